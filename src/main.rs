@@ -1,13 +1,24 @@
-use actix_web::{get, post, web, App, HttpResponse, HttpServer, Responder};
+use actix_web::{get, post, web, App, HttpRequest, HttpResponse, HttpServer, Responder};
+use actix_web::dev::{Body, Service};
+use actix_http::ws;
+use actix_codec::{Decoder, Encoder};
+use futures::StreamExt;
+use actix_web::http::StatusCode;
 use actix_files::NamedFile;
 use serde::{Serialize, Deserialize};
 use dotenv::dotenv;
-use tokio::sync::RwLock;
-use std::sync::atomic::{Ordering, AtomicUsize};
+use tokio::sync::{RwLock, RwLockWriteGuard, Mutex, Notify};
+use std::sync::atomic::{Ordering, AtomicUsize, AtomicU64, AtomicBool};
 use rand::seq::SliceRandom;
+use rand::Rng;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use std::fs;
 use std::path::Path;
 use std::env;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 
 //fallback standards in case the ENV variable does not exist
@@ -16,6 +27,45 @@ const INITIAL_MONEY:i64 = 500; //initial amount of money every player owns
 const INITIAL_JOKERS:usize = 3; //number of inital jokers every player gets
 const NORMAL_Q_MONEY:i64 = 500; //money to get when answering a normal question correctly
 const ESTIMATION_Q_MONEY:i64 = 1000; //money to get when winning a estimation question
+const MULTI_SELECT_Q_MONEY:i64 = 500; //money to get when selecting exactly the correct answers of a multi-select question
+const ANSWER_GRACE_SECS:u64 = 0; //seconds players may still change their answers after everyone answered
+const COMEBACK_MULTIPLIER:f64 = 1.0; //multiplier for the reward of the player(s) with the least money (if behind the leader)
+const MONEY_FLOOR:i64 = 1; //minimum money of a player, so that everyone can continue the game
+const STRICT_GIVE_MONEY:bool = false; //reject giveMoney deductions exceeding the player's money instead of clamping
+const ANSWER_LOCK:bool = false; //answers are final once submitted (else they can be changed until everyone answered)
+const AUTO_START:bool = false; //automatically start the first question once MIN_PLAYERS joined
+const MIN_PLAYERS:usize = 1; //number of players needed for AUTO_START
+const SAVE_INTERVAL_MS:u64 = 1000; //minimum time between two saves of the game state to STATE_FILE
+const BONUS_FASTEST_MONEY:i64 = 200; //bonus money for the fastest correct answerer, given by the host with bonusFastest
+const POLL_TIMEOUT_SECS:u64 = 25; //maximum time a long-poll request waits for new events
+const END_GAME_SCORING:bool = true; //score the given answers when the host ends the game during answering (else they are discarded)
+const CATEGORY_TEASER:bool = false; //announce the category first and show the question after revealQuestion
+const VERSUS_MAX_TARGETS:usize = 1; //maximum number of players a player can attack in a versus question
+const QUESTION_LIMIT:usize = 0; //number of questions to play before the game ends (0 = all questions)
+const GAME_MODE:&str = "Money"; //Money or Lives (wrong answers cost a life instead of money, the last player left wins)
+const INITIAL_LIVES:usize = 3; //number of lives every player gets in GAME_MODE Lives
+const JOKER_REPLENISH_EVERY:usize = 0; //give every player an additional joker after every N played questions (0 = never)
+const MAX_JOKERS:usize = 0; //maximum number of jokers reachable by the replenishment (0 = no maximum)
+const ESTIMATION_TIE_POLICY:&str = "AllGetFull"; //reward of tied closest players in estimation questions: AllGetFull or SplitEqually
+const DEFAULT_BET:i64 = 0; //bet of players without a bet when the host forces the answering (0 = they neither win nor lose)
+const MAX_MISSED_ROUNDS:usize = 0; //kick players automatically after this many questions in a row without answer (0 = never)
+const BET_LOCK_DELAY_SECS:u64 = 0; //seconds between locking the bets (BetsLocked event) and showing the betting question
+const VERSUS_ROUNDING:&str = "Truncate"; //rounding of the money in versus questions: Truncate (e.g. 101 / 2 = 50), Round or Ceil (both 51)
+const CONFIRM_GAME_END:bool = false; //wait for the host's confirmGameEnd after the last question before the game ends
+const KICK_GRACE_SECS:u64 = 0; //seconds a kicked player can still reconnect (with the right PIN) before the slot is freed, 0 = kick immediately
+const SAFE_MONEY:i64 = 0; //money that can not be lost in betting and versus questions (players below it can not lose more), 0 = disabled
+const TRANSITION_HISTORY:usize = 100; //number of the last state transitions kept for GET transitions
+const MAX_MONEY:i64 = 1_000_000_000_000; //maximum money of a player, so that repeated doubling can not overflow
+const STEAL_AMOUNT:i64 = 100; //money every correct answerer of a steal question takes from a random wrong answerer
+const ANNOUNCEMENT_MAX_LENGTH:usize = 200; //maximum number of characters of a host announcement (longer ones are cut)
+const AUDIENCE_POLL_FROM_ANSWERS:bool = false; //derive the audience poll joker from the other players' answers instead of randomly
+const AUDIENCE_POLL_MIN_ANSWERS:usize = 3; //number of answers needed for the audience poll joker with AUDIENCE_POLL_FROM_ANSWERS
+const GLOBAL_QUESTION_TIMEOUT_SECS:u64 = 0; //seconds after which betting, selecting and answering are forced to end like forceQuestionAnswering/forceQuestionResults (0 = never)
+const TIEBREAKER_COIN_FLIP:bool = false; //resolve a tie of the winners at the game's end by a coin flip (TiebreakerResolved event)
+const OPEN_BETTING:bool = false; //show everyone's bets in the public player data and BeginBettingQAnswering event while answering betting questions
+const TIME_BUDGET_MS:u64 = 0; //time budget of each player for answering across all questions, used up from each question's begin to the answer (0 = unlimited)
+const REQUEST_LOG:bool = true; //log method, path, status and duration of every request
+const REVEAL_MODE:&str = "AllAtOnce"; //how players' answers are revealed in the results: AllAtOnce or OneByOne (by the host)
 
 //struct for player data
 #[derive(Serialize, Deserialize, Clone)]
@@ -26,8 +76,33 @@ struct PlayerData
     money: i64,
     //could also use Option<>, but easier for frontend to handle without
     money_bet: i64,
-    vs_player: String,
+    vs_player: String, //first attacked player (for compatibility)
+    #[serde(default)]
+    vs_players: Vec<String>, //all attacked players in versus questions
     answer: usize,
+    answers_selected: Vec<usize>, //selected answers for multi-select questions
+    estimate_f: Option<f64>, //decimal answer for estimation questions
+    missed_rounds: usize, //number of questions in a row the player did not answer
+    disabled: bool, //disabled by the host: can not take part in the questions, but keeps money and jokers
+    lives: usize, //remaining lives in GAME_MODE Lives (eliminated at zero)
+    #[serde(default)]
+    eliminated: bool, //lost the last life in GAME_MODE Lives (stays visible with the final standing)
+    #[serde(default)]
+    elimination_order: Option<usize>, //1 for the first eliminated players, 2 for the next ones, ... (same round = same number)
+    correct_count: usize, //number of correctly answered questions (independent of money)
+    answered_count: usize, //number of answered questions
+    #[serde(default)]
+    streak: usize, //number of correct answers in a row
+    #[serde(default)]
+    time_budget_ms: Option<u64>, //remaining time budget for answering with TIME_BUDGET_MS (None = unlimited, players are skipped at 0)
+    #[serde(skip)]
+    answered_at: Option<Instant>, //time of the player's last answer to find the fastest correct answerer
+    #[serde(skip)]
+    removed_answers: Vec<usize>, //answers removed by the player's jokers in the current question (not public)
+    #[serde(skip)]
+    bonus_spun: bool, //whether the player already spun the bonus wheel after the current question
+    #[serde(skip)]
+    pending_removal: Option<Instant>, //time when a soft kicked player is removed, unless reconnecting before
 }
 
 //different gameshow question types
@@ -38,6 +113,7 @@ enum QuestionType
     BettingQuestion,
     EstimationQuestion,
     VersusQuestion,
+    MultiSelectQuestion,
 }
 
 //struct for question data
@@ -49,6 +125,22 @@ struct Question
     question: String,
     answers: Vec<String>,
     correct_answer: usize,
+    #[serde(default)]
+    correct_answers: Vec<usize>, //all correct answers for multi-select questions
+    accepted_answers: Option<Vec<usize>>, //optional alternative answers that are also counted as correct
+    correct_answer_f: Option<f64>, //decimal correct answer for estimation questions (used instead of correct_answer if given)
+    explanation: Option<String>, //optional explanation of the correct answer shown with the results
+    fun_fact: Option<String>, //optional fun fact related to the question shown with the results
+    max_bet: Option<i64>, //optional maximum bet for betting questions
+    min_bet: Option<i64>, //optional minimum bet for betting questions (players with less money can only go all-in)
+    bet_payout_ratio: Option<f64>, //optional payout of a correct bet as multiple of the bet for betting questions (default 1.0)
+    bet_speed_bonus: Option<i64>, //optional bonus money for the fastest correct bettor in betting questions (on top of the payout)
+    answer_time_secs: Option<u64>, //optional seconds after which answering is forced to end (instead of GLOBAL_QUESTION_TIMEOUT_SECS)
+    bet_time_secs: Option<u64>, //optional seconds after which betting is forced to end in betting questions (instead of GLOBAL_QUESTION_TIMEOUT_SECS)
+    #[serde(default)]
+    steal: bool, //normal questions only: every correct answerer also steals STEAL_AMOUNT from a random wrong answerer
+    #[serde(default)]
+    tags: Vec<String>, //optional tags (e.g. themes) to load filtered subsets with loadQuestionsFiltered
 }
 
 
@@ -70,10 +162,21 @@ struct EventBeginBettingQBetting
     category: String,
 }
 #[derive(Serialize, Deserialize, Clone)]
+struct EventBetsLocked
+{
+    num_players: usize, //number of players with a bet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bets: Option<Vec<(String, i64)>>, //name and bet of every player with OPEN_BETTING
+}
+#[derive(Serialize, Deserialize, Clone)]
 struct EventBeginBettingQAnswering
 {
+    current_question: usize,
+    num_questions: usize,
     question: String,
     answers: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bets: Option<Vec<(String, i64)>>, //name and bet of every player with OPEN_BETTING
 }
 #[derive(Serialize, Deserialize, Clone)]
 struct EventBeginEstimationQAnswering
@@ -93,20 +196,141 @@ struct EventBeginVersusQSelecting
 #[derive(Serialize, Deserialize, Clone)]
 struct EventBeginVersusQAnswering
 {
+    current_question: usize,
+    num_questions: usize,
+    question: String,
+    answers: Vec<String>,
+}
+#[derive(Serialize, Deserialize, Clone)]
+struct EventBeginMultiSelectQAnswering
+{
+    question_type: QuestionType,
+    current_question: usize,
+    category: String,
     question: String,
     answers: Vec<String>,
 }
 #[derive(Serialize, Deserialize, Clone)]
+struct EventCategoryTeaser
+{
+    question_type: QuestionType,
+    current_question: usize,
+    category: String,
+}
+#[derive(Serialize, Deserialize, Clone)]
 struct EventShowResults
 {
+    question_index: usize, //number of the scored question
     correct_answer: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    correct_answers: Vec<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correct_answer_f: Option<f64>, //the correct value of estimation questions (decimal or the correct_answer)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explanation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fun_fact: Option<String>,
     previous_player_data: Vec<PlayerData>,
     player_data: Vec<PlayerData>,
 }
 #[derive(Serialize, Deserialize, Clone)]
+struct EventRoundSummary
+{
+    question_index: usize,
+    correct_answer: usize,
+    winners: Vec<String>, //players who gained money in this round
+    biggest_gain: Option<(String, i64)>, //player with the biggest gain and the gain
+}
+#[derive(Serialize, Deserialize, Clone)]
+struct EventRevealPlayer
+{
+    name: String,
+    answer: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    answers_selected: Vec<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimate_f: Option<f64>,
+    correct: bool,
+}
+#[derive(Serialize, Deserialize, Clone)]
+struct EventPlayerOnFloor
+{
+    name: String,
+}
+#[derive(Serialize, Deserialize, Clone)]
+struct EventVersusTargetMissing
+{
+    attacker: String,
+    target: String, //attacked player, who was kicked in the meantime
+}
+#[derive(Serialize, Deserialize, Clone)]
+struct EventDefaultBetApplied
+{
+    players: Vec<String>, //players without a bet when the answering was forced
+    money_bet: i64, //bet they got (0 = they are not scored)
+}
+#[derive(Serialize, Deserialize, Clone)]
+struct EventBetClamped
+{
+    name: String,
+    money_bet: i64, //the bet that was bigger than the money
+    money: i64, //the money the bet was clamped to
+}
+#[derive(Serialize, Deserialize, Clone)]
+struct EventPlayerJoined
+{
+    name: String,
+}
+#[derive(Serialize, Deserialize, Clone)]
+struct EventPlayerLeft
+{
+    name: String,
+}
+#[derive(Serialize, Deserialize, Clone)]
+struct EventAnnouncement
+{
+    message: String,
+}
+#[derive(Serialize, Deserialize, Clone)]
+struct EventQuestionsLoaded
+{
+    count: usize,
+}
+#[derive(Serialize, Deserialize, Clone)]
+struct EventQuestionQueueChanged
+{
+    current: usize, //number of the question before the next one
+    total: usize,
+}
+#[derive(Serialize, Deserialize, Clone)]
+struct EventReadyToEndGame
+{
+    current_question: usize, //number of the last played question
+}
+#[derive(Serialize, Deserialize, Clone)]
+struct EventBonusSpin
+{
+    name: String,
+    multiplier: f64,
+}
+#[derive(Serialize, Deserialize, Clone)]
+struct EventNewGameStarted
+{
+    num_questions: usize,
+    reset_players: bool, //whether the players' money and jokers were reset
+}
+#[derive(Serialize, Deserialize, Clone)]
+struct EventTiebreakerResolved
+{
+    winner: String,
+    method: String, //how the tie was resolved: CoinFlip
+}
+#[derive(Serialize, Deserialize, Clone)]
 struct EventGameEnding
 {
     player_data: Vec<PlayerData>,
+    winners: Vec<String>, //all players with the most money (more than one on a tie)
+    winning_money: i64,
 }
 //combining struct for events
 #[derive(Serialize, Deserialize, Clone)]
@@ -114,12 +338,30 @@ enum EventType
 {
     BeginNormalQAnswering(EventBeginNormalQAnswering),
     BeginBettingQBetting(EventBeginBettingQBetting),
+    BetsLocked(EventBetsLocked),
     BeginBettingQAnswering(EventBeginBettingQAnswering),
     BeginEstimationQAnswering(EventBeginEstimationQAnswering),
     BeginVersusQSelecting(EventBeginVersusQSelecting),
     BeginVersusQAnswering(EventBeginVersusQAnswering),
+    BeginMultiSelectQAnswering(EventBeginMultiSelectQAnswering),
+    CategoryTeaser(EventCategoryTeaser),
     ShowResults(EventShowResults),
+    RoundSummary(EventRoundSummary),
+    RevealPlayer(EventRevealPlayer),
+    PlayerOnFloor(EventPlayerOnFloor),
+    VersusTargetMissing(EventVersusTargetMissing),
+    DefaultBetApplied(EventDefaultBetApplied),
+    BetClamped(EventBetClamped),
+    PlayerJoined(EventPlayerJoined),
+    PlayerLeft(EventPlayerLeft),
+    Announcement(EventAnnouncement),
+    QuestionsLoaded(EventQuestionsLoaded),
+    QuestionQueueChanged(EventQuestionQueueChanged),
+    ReadyToEndGame(EventReadyToEndGame),
     GameEnding(EventGameEnding),
+    TiebreakerResolved(EventTiebreakerResolved),
+    NewGameStarted(EventNewGameStarted),
+    BonusSpin(EventBonusSpin),
 }
 #[derive(Serialize, Deserialize, Clone)]
 struct Event
@@ -133,25 +375,119 @@ struct Event
 enum QuestionState
 { //the bool indicates if it is ready to transition to next state
     Results(bool),
+    CategoryTeaser(bool),
     NormalQAnswering(bool),
     BettingQBetting(bool),
     BettingQAnswering(bool),
     EstimationQAnswering(bool),
     VersusQSelecting(bool),
     VersusQAnswering(bool),
+    MultiSelectQAnswering(bool),
+    AwaitingGameEnd,
     GameEnding,
 }
 
 
-//database of all shared data for the gameshow
-//lock order to avoid deadlocks: current_question_state -> questions -> player_data -> game_events
+//database of all shared data for the gameshow, a single instance is shared by all HTTP workers and the background tasks
+//state transitions only happen while holding the current_question_state write lock, which makes them safe with multiple workers
+//lock order to avoid deadlocks: current_question_state -> answers_ready_at -> bets_locked_until -> state_began_at -> questions -> player_data -> player_index -> player_pins -> game_events -> transitions -> rng
 struct GameshowData
 {
+    config: Config, //configuration read at startup
     player_data: RwLock<Vec<PlayerData>>,
     questions: RwLock<Vec<Question>>,
-    game_events: RwLock<Vec<Event>>,
-    current_question: AtomicUsize,
+    player_index: RwLock<HashMap<String, usize>>, //index of each player in player_data by name, kept in sync on join and kick
+    player_pins: RwLock<HashMap<String, String>>, //optional PINs of the players to protect reclaiming their slot
+    game_events: RwLock<Vec<Event>>, //written with write_events, so that the long-polls are woken up
+    events_written: Notify, //wakes up the long-polls waiting for new events
+    roster_version: AtomicU64, //increased on every change of player_data to let clients detect changes
+    current_question: AtomicUsize, //only changed while holding the current_question_state write lock
+    questions_played: AtomicUsize, //number of questions begun since loading the questions (for QUESTION_LIMIT)
+    jokers_used: AtomicUsize, //number of jokers used since loading the questions
     current_question_state: RwLock<QuestionState>,
+    answers_ready_at: Mutex<Option<Instant>>, //time when all players answered, to allow answer changes in the grace period
+    bets_locked_until: Mutex<Option<Instant>>, //time until the betting question is shown after the bets were locked
+    state_began_at: Mutex<Instant>, //time of the last state transition in check_state_add_events (for GLOBAL_QUESTION_TIMEOUT_SECS)
+    auto_started: AtomicBool, //whether AUTO_START already started the game
+    state_dirty: AtomicBool, //whether there were state transitions since the last save
+    fastest_bonus_question: AtomicUsize, //number of the last question the fastest bonus was given for (0 = none)
+    transitions: Mutex<Vec<(QuestionState, u64)>>, //last state transitions with the unix time in ms (for debugging the pacing)
+    rng: Mutex<StdRng>, //all randomness (jokers etc.) is drawn from here to be reproducible with RNG_SEED
+}
+
+impl GameshowData
+{
+    //create the game without players, before the first question
+    fn new(config: Config, questions: Vec<Question>) -> GameshowData
+    {
+        //seed the random number generator for reproducible runs if wanted
+        let rng = match config.rng_seed
+        {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        GameshowData {
+            config,
+            player_data: RwLock::new(Vec::new()),
+            questions: RwLock::new(questions),
+            player_index: RwLock::new(HashMap::new()),
+            player_pins: RwLock::new(HashMap::new()),
+            game_events: RwLock::new(Vec::new()),
+            events_written: Notify::new(),
+            roster_version: AtomicU64::new(0),
+            current_question: AtomicUsize::new(0),
+            questions_played: AtomicUsize::new(0),
+            jokers_used: AtomicUsize::new(0),
+            current_question_state: RwLock::new(QuestionState::Results(false)),
+            answers_ready_at: Mutex::new(None),
+            bets_locked_until: Mutex::new(None),
+            state_began_at: Mutex::new(Instant::now()),
+            auto_started: AtomicBool::new(false),
+            state_dirty: AtomicBool::new(false),
+            fastest_bonus_question: AtomicUsize::new(0),
+            transitions: Mutex::new(Vec::new()),
+            rng: Mutex::new(rng),
+        }
+    }
+    
+    //lock the game events for writing, the long-polls are woken up when the lock is released
+    async fn write_events(&self) -> EventsWriteGuard<'_>
+    {
+        EventsWriteGuard { events: self.game_events.write().await, written: &self.events_written }
+    }
+}
+
+//write lock of the game events, which notifies the waiting long-polls when dropped
+struct EventsWriteGuard<'a>
+{
+    events: RwLockWriteGuard<'a, Vec<Event>>,
+    written: &'a Notify,
+}
+
+impl Deref for EventsWriteGuard<'_>
+{
+    type Target = Vec<Event>;
+    
+    fn deref(&self) -> &Vec<Event>
+    {
+        &self.events
+    }
+}
+
+impl DerefMut for EventsWriteGuard<'_>
+{
+    fn deref_mut(&mut self) -> &mut Vec<Event>
+    {
+        &mut self.events
+    }
+}
+
+impl Drop for EventsWriteGuard<'_>
+{
+    fn drop(&mut self)
+    {
+        self.written.notify_waiters();
+    }
 }
 
 
@@ -160,870 +496,6764 @@ async fn read_questions(filename: impl AsRef<Path>) -> std::io::Result<Vec<Quest
 {
     let json_string = fs::read_to_string(filename)?;
     let questions: Vec<Question> = serde_json::from_str(&json_string)?;
+    validate_questions(&questions).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
     Ok(questions)
 }
 
-//check if next question state is possible/initiated and transition
-//(by preparing everything and adding an event)
-async fn check_state_add_events(data: web::Data<GameshowData>)
+//read questions from a CSV file with the columns: type, category, question, answer 1 .. answer N, correct
+//(correct is the answer ID, a decimal number for estimation questions or the answer IDs separated by ';' for multi-select questions)
+async fn read_questions_csv(filename: impl AsRef<Path>) -> std::io::Result<Vec<Question>>
 {
-    let mut question_state = data.current_question_state.write().await;
-    match *question_state
+    let questions = parse_questions_csv(filename)?;
+    validate_questions(&questions).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+    Ok(questions)
+}
+
+//parse the rows of a CSV question file, only checking what is needed to build the questions
+fn parse_questions_csv(filename: impl AsRef<Path>) -> std::io::Result<Vec<Question>>
+{
+    let invalid_data = |err: String| std::io::Error::new(std::io::ErrorKind::InvalidData, err);
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_path(filename)
+        .map_err(|err| invalid_data(err.to_string()))?;
+    let mut questions = Vec::new();
+    for (i, record) in reader.records().enumerate()
     {
-        QuestionState::Results(true) => { //transition to next question (different states for different questions)
-            //gather necessary data
-            let question_id = data.current_question.fetch_add(1, Ordering::Relaxed) + 1;
-            let questions = data.questions.read().await;
-            let num_questions = (*questions).len();
-            if question_id > num_questions
-            { //game ending
-                let access = data.player_data.read().await;
-                let player_data = (*access).clone();
-                //create event
-                let mut events = data.game_events.write().await;
-                let mut event_id = 0;
-                let last = (*events).last();
-                if last.is_some()
-                {
-                    event_id = last.unwrap().id + 1;
-                }
-                let new_event = Event { id: event_id, event_name: String::from("GameEnding"),
-                    event: EventType::GameEnding(EventGameEnding { player_data: player_data }) };
-                (*events).push(new_event);
-                //set new question state
-                *question_state = QuestionState::GameEnding;
-            }
-            else
-            { //next question
-                let question_type = (*questions)[question_id - 1].question_type.clone();
-                let category = (*questions)[question_id - 1].category.clone();
-                let question = (*questions)[question_id - 1].question.clone();
-                let answers = (*questions)[question_id - 1].answers.clone();
-                //reset bets and question answers for all players
-                let mut player_access = data.player_data.write().await;
-                for player in (*player_access).iter_mut()
-                { //change zeros to None when using Options
-                    player.money_bet = 0;
-                    player.vs_player = "".to_owned();
-                    player.answer = 0;
-                }
-                //depending on question type begin different question-specific event
-                let mut events = data.game_events.write().await;
-                let mut event_id = 0;
-                let last = (*events).last();
-                if last.is_some()
-                {
-                    event_id = last.unwrap().id + 1;
-                }
-                match question_type
-                {
-                    QuestionType::NormalQuestion => {
-                        let event_data = EventBeginNormalQAnswering { question_type: question_type, current_question: question_id,
-                            category: category, question: question, answers: answers };
-                        let new_event = Event { id: event_id, event_name: String::from("BeginNormalQAnswering"),
-                            event: EventType::BeginNormalQAnswering(event_data) };
-                        (*events).push(new_event);
-                        //set new question state
-                        *question_state = QuestionState::NormalQAnswering(false);
-                    },
-                    QuestionType::BettingQuestion => {
-                        let event_data = EventBeginBettingQBetting { question_type: question_type, current_question: question_id, category: category };
-                        let new_event = Event { id: event_id, event_name: String::from("BeginBettingQBetting"),
-                            event: EventType::BeginBettingQBetting(event_data) };
-                        (*events).push(new_event);
-                        //set new question state
-                        *question_state = QuestionState::BettingQBetting(false);
-                    },
-                    QuestionType::EstimationQuestion => {
-                        let event_data = EventBeginEstimationQAnswering { question_type: question_type, current_question: question_id, category: category,
-                            question: question };
-                        let new_event = Event { id: event_id, event_name: String::from("BeginEstimationQAnswering"),
-                            event: EventType::BeginEstimationQAnswering(event_data) };
-                        (*events).push(new_event);
-                        //set new question state
-                        *question_state = QuestionState::EstimationQAnswering(false);
-                    },
-                    QuestionType::VersusQuestion => {
-                        let event_data = EventBeginVersusQSelecting { question_type: question_type, current_question: question_id, category: category };
-                        let new_event = Event { id: event_id, event_name: String::from("BeginVersusQSelecting"),
-                            event: EventType::BeginVersusQSelecting(event_data) };
-                        (*events).push(new_event);
-                        //set new question state
-                        *question_state = QuestionState::VersusQSelecting(false);
-                    },
-                }
-                
-                
-            }
-        },
-        QuestionState::BettingQBetting(true) => { //transition to answering state
-            //gather necessary data
-            let question_id = data.current_question.load(Ordering::Relaxed);
-            let questions = data.questions.read().await;
-            let question = (*questions)[question_id - 1].question.clone();
-            let answers = (*questions)[question_id - 1].answers.clone();
-            //create event
-            let mut events = data.game_events.write().await;
-            let mut event_id = 0;
-            let last = (*events).last();
-            if last.is_some()
-            {
-                event_id = last.unwrap().id + 1;
-            }
-            let event_data = EventBeginBettingQAnswering { question: question, answers: answers };
-            let new_event = Event { id: event_id, event_name: String::from("BeginBettingQAnswering"),
-                event: EventType::BeginBettingQAnswering(event_data) };
-            (*events).push(new_event);
-            //set new question state
-            *question_state = QuestionState::BettingQAnswering(false);
-        },
-        QuestionState::VersusQSelecting(true) => { //transition to answering state
-            //gather necessary data
-            let question_id = data.current_question.load(Ordering::Relaxed);
-            let questions = data.questions.read().await;
-            let question = (*questions)[question_id - 1].question.clone();
-            let answers = (*questions)[question_id - 1].answers.clone();
-            //create event
-            let mut events = data.game_events.write().await;
-            let mut event_id = 0;
-            let last = (*events).last();
-            if last.is_some()
-            {
-                event_id = last.unwrap().id + 1;
-            }
-            let event_data = EventBeginVersusQAnswering { question: question, answers: answers };
-            let new_event = Event { id: event_id, event_name: String::from("BeginVersusQAnswering"),
-                event: EventType::BeginVersusQAnswering(event_data) };
-            (*events).push(new_event);
-            //set new question state
-            *question_state = QuestionState::VersusQAnswering(false);
-        },
-        QuestionState::NormalQAnswering(true) => { //transition to results state
-            //gather necessary data
-            let question_id = data.current_question.load(Ordering::Relaxed);
-            let questions = data.questions.read().await;
-            let correct_answer = (*questions)[question_id - 1].correct_answer;
-            //compute the new money of each player
-            let mut access = data.player_data.write().await;
-            let previous_player_data = (*access).clone();
-            for player in (*access).iter_mut()
-            {
-                if player.answer == correct_answer
+        let record = record.map_err(|err| invalid_data(err.to_string()))?;
+        let fields: Vec<String> = record.iter().map(|field| String::from(field.trim())).collect();
+        if fields.len() < 4
+        {
+            return Err(invalid_data(format!("Question {}: at least type, category, question and correct are needed!", i + 1)));
+        }
+        let question_type: QuestionType = serde_json::from_value(serde_json::Value::String(fields[0].clone()))
+            .map_err(|_| invalid_data(format!("Question {}: unknown type {}!", i + 1, fields[0])))?;
+        let correct = &fields[fields.len() - 1];
+        let mut correct_answer = 0;
+        let mut correct_answers = Vec::new();
+        let mut correct_answer_f = None;
+        let invalid_correct = || invalid_data(format!("Question {}: invalid correct answer {}!", i + 1, correct));
+        match question_type
+        {
+            QuestionType::MultiSelectQuestion => {
+                for answer in correct.split(';')
                 {
-                    let normal_q_money = env::var("NORMAL_Q_MONEY").unwrap_or_default().parse().unwrap_or(NORMAL_Q_MONEY);
-                    player.money += normal_q_money;
+                    correct_answers.push(answer.trim().parse().map_err(|_| invalid_correct())?);
                 }
-            }
-            let player_data = (*access).clone();
-            //create event
-            let mut events = data.game_events.write().await;
-            let mut event_id = 0;
-            let last = (*events).last();
-            if last.is_some()
+            },
+            QuestionType::EstimationQuestion if correct.contains('.') => {
+                correct_answer_f = Some(correct.parse().map_err(|_| invalid_correct())?);
+            },
+            _ => { correct_answer = correct.parse().map_err(|_| invalid_correct())?; },
+        }
+        questions.push(Question { question_type, category: fields[1].clone(), question: fields[2].clone(),
+            answers: fields[3 .. fields.len() - 1].to_vec(), correct_answer, correct_answers,
+            accepted_answers: None, correct_answer_f, explanation: None, fun_fact: None, max_bet: None, min_bet: None, bet_payout_ratio: None,
+            bet_speed_bonus: None, answer_time_secs: None, bet_time_secs: None, steal: false, tags: Vec::new() });
+    }
+    Ok(questions)
+}
+
+//check the questions for invalid data, the first problem is returned
+fn validate_questions(questions: &[Question]) -> Result<(), String>
+{
+    for (i, question) in questions.iter().enumerate()
+    {
+        if let Some(error) = question_errors(question).first()
+        {
+            return Err(format!("Question {}: {}", i + 1, error));
+        }
+    }
+    Ok(())
+}
+
+//all problems of the question's data
+fn question_errors(question: &Question) -> Vec<String>
+{
+    let mut errors = Vec::new();
+    if let Some(accepted_answers) = &question.accepted_answers
+    {
+        if accepted_answers.iter().any(|answer| *answer < 1 || *answer > question.answers.len())
+        {
+            errors.push(String::from("accepted_answers must be 1 - len(answers)!"));
+        }
+    }
+    if question.min_bet.is_some_and(|min_bet| min_bet < 1 || question.max_bet.is_some_and(|max_bet| min_bet > max_bet))
+    {
+        errors.push(String::from("min_bet must be >= 1 and <= max_bet!"));
+    }
+    if question.bet_payout_ratio.is_some_and(|ratio| !ratio.is_finite() || ratio < 0.0)
+    {
+        errors.push(String::from("bet_payout_ratio must be a finite number >= 0!"));
+    }
+    if question.bet_speed_bonus.is_some_and(|bonus| bonus < 0)
+    {
+        errors.push(String::from("bet_speed_bonus must be >= 0!"));
+    }
+    if question.answer_time_secs == Some(0) || question.bet_time_secs == Some(0)
+    {
+        errors.push(String::from("answer_time_secs and bet_time_secs must be >= 1!"));
+    }
+    if question.steal && question.question_type != QuestionType::NormalQuestion
+    {
+        errors.push(String::from("steal is only possible for normal questions!"));
+    }
+    errors
+}
+
+//check if the answer is correct for the question (the correct answer or one of the accepted answers)
+fn is_correct_answer(question: &Question, answer: usize) -> bool
+{
+    answer == question.correct_answer || question.accepted_answers.as_ref().is_some_and(|accepted_answers| accepted_answers.contains(&answer))
+}
+
+//configuration read once at startup from the ENV variables (with the constants above as defaults)
+#[derive(Serialize)]
+struct Config
+{
+    questions_file: String,
+    initial_money: i64,
+    initial_jokers: usize,
+    normal_q_money: i64,
+    estimation_q_money: i64,
+    multi_select_q_money: i64,
+    answer_grace_secs: u64,
+    comeback_multiplier: f64,
+    money_floor: i64,
+    strict_give_money: bool,
+    answer_lock: bool,
+    auto_start: bool,
+    min_players: usize,
+    save_interval_ms: u64,
+    bonus_fastest_money: i64,
+    poll_timeout_secs: u64,
+    end_game_scoring: bool,
+    category_teaser: bool,
+    versus_max_targets: usize,
+    question_limit: usize,
+    game_mode: String,
+    initial_lives: usize,
+    joker_replenish_every: usize,
+    max_jokers: usize,
+    estimation_tie_policy: String,
+    default_bet: i64,
+    max_missed_rounds: usize,
+    bet_lock_delay_secs: u64,
+    versus_rounding: String,
+    confirm_game_end: bool,
+    kick_grace_secs: u64,
+    safe_money: i64,
+    transition_history: usize,
+    max_money: i64,
+    steal_amount: i64,
+    announcement_max_length: usize,
+    audience_poll_from_answers: bool,
+    audience_poll_min_answers: usize,
+    global_question_timeout_secs: u64,
+    tiebreaker_coin_flip: bool,
+    open_betting: bool,
+    time_budget_ms: u64,
+    request_log: bool,
+    reveal_mode: String,
+    category_multipliers: HashMap<String, f64>, //reward multipliers of the categories (e.g. {"Science": 2.0})
+    allowed_bets: Vec<i64>, //the only allowed bets (empty = all)
+    bonus_wheel: Vec<f64>, //multipliers of the bonus wheel, equally likely (empty = no bonus wheel)
+    streak_bonus: Vec<(usize, i64)>, //bonus money for reaching a streak of correct answers (e.g. [(3, 100), (5, 300)])
+    state_file: Option<String>,
+    rng_seed: Option<u64>,
+    #[serde(skip_serializing)]
+    admin_token: Option<String>, //token of the host for the endpoints leaking questions or answers (not printed at startup)
+}
+
+//read the ENV variable, the default if it is not set or empty and an error if it can not be parsed
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> Result<T, String>
+{
+    match env::var(name)
+    {
+        Ok(value) if !value.trim().is_empty() => value.trim().parse().map_err(|_| format!("{} has the invalid value \"{}\"!", name, value)),
+        _ => Ok(default),
+    }
+}
+
+impl Config
+{
+    //read the configuration from the ENV variables and validate it
+    fn from_env() -> Result<Config, String>
+    {
+        //CATEGORY_MULTIPLIERS is a JSON object (e.g. {"Science": 2.0}) or the path to a JSON file containing it
+        let category_multipliers = env::var("CATEGORY_MULTIPLIERS").unwrap_or_default();
+        let category_multipliers: HashMap<String, f64> = if category_multipliers.trim().is_empty() { HashMap::new() }
+        else
+        {
+            let json_string = if category_multipliers.trim_start().starts_with('{') { category_multipliers.clone() }
+                else { fs::read_to_string(&category_multipliers).map_err(|err| format!("CATEGORY_MULTIPLIERS file could not be read: {}!", err))? };
+            serde_json::from_str(&json_string).map_err(|err| format!("CATEGORY_MULTIPLIERS is invalid: {}!", err))?
+        };
+        //ALLOWED_BETS is comma-separated
+        let mut allowed_bets = Vec::new();
+        for bet in env::var("ALLOWED_BETS").unwrap_or_default().split(',').filter(|bet| !bet.trim().is_empty())
+        {
+            allowed_bets.push(bet.trim().parse().map_err(|_| format!("ALLOWED_BETS has the invalid bet \"{}\"!", bet))?);
+        }
+        //BONUS_WHEEL is comma-separated, a multiplier can be listed multiple times to make it more likely
+        let mut bonus_wheel = Vec::new();
+        for multiplier in env::var("BONUS_WHEEL").unwrap_or_default().split(',').filter(|multiplier| !multiplier.trim().is_empty())
+        {
+            bonus_wheel.push(multiplier.trim().parse().map_err(|_| format!("BONUS_WHEEL has the invalid multiplier \"{}\"!", multiplier))?);
+        }
+        //STREAK_BONUS is comma-separated with streak:bonus pairs (e.g. 3:100,5:300)
+        let mut streak_bonus = Vec::new();
+        for milestone in env::var("STREAK_BONUS").unwrap_or_default().split(',').filter(|milestone| !milestone.trim().is_empty())
+        {
+            let invalid = || format!("STREAK_BONUS has the invalid milestone \"{}\"!", milestone);
+            let (streak, bonus) = milestone.split_once(':').ok_or_else(invalid)?;
+            streak_bonus.push((streak.trim().parse().map_err(|_| invalid())?, bonus.trim().parse().map_err(|_| invalid())?));
+        }
+        let config = Config {
+            questions_file: env_or("QUESTIONS_FILE", String::from(QUESTIONS_FILE))?,
+            initial_money: env_or("INITIAL_MONEY", INITIAL_MONEY)?,
+            initial_jokers: env_or("INITIAL_JOKERS", INITIAL_JOKERS)?,
+            normal_q_money: env_or("NORMAL_Q_MONEY", NORMAL_Q_MONEY)?,
+            estimation_q_money: env_or("ESTIMATION_Q_MONEY", ESTIMATION_Q_MONEY)?,
+            multi_select_q_money: env_or("MULTI_SELECT_Q_MONEY", MULTI_SELECT_Q_MONEY)?,
+            answer_grace_secs: env_or("ANSWER_GRACE_SECS", ANSWER_GRACE_SECS)?,
+            comeback_multiplier: env_or("COMEBACK_MULTIPLIER", COMEBACK_MULTIPLIER)?,
+            money_floor: env_or("MONEY_FLOOR", MONEY_FLOOR)?,
+            strict_give_money: env_or("STRICT_GIVE_MONEY", STRICT_GIVE_MONEY)?,
+            answer_lock: env_or("ANSWER_LOCK", ANSWER_LOCK)?,
+            auto_start: env_or("AUTO_START", AUTO_START)?,
+            min_players: env_or("MIN_PLAYERS", MIN_PLAYERS)?,
+            save_interval_ms: env_or("SAVE_INTERVAL_MS", SAVE_INTERVAL_MS)?,
+            bonus_fastest_money: env_or("BONUS_FASTEST_MONEY", BONUS_FASTEST_MONEY)?,
+            poll_timeout_secs: env_or("POLL_TIMEOUT_SECS", POLL_TIMEOUT_SECS)?,
+            end_game_scoring: env_or("END_GAME_SCORING", END_GAME_SCORING)?,
+            category_teaser: env_or("CATEGORY_TEASER", CATEGORY_TEASER)?,
+            versus_max_targets: env_or("VERSUS_MAX_TARGETS", VERSUS_MAX_TARGETS)?,
+            question_limit: env_or("QUESTION_LIMIT", QUESTION_LIMIT)?,
+            game_mode: env_or("GAME_MODE", String::from(GAME_MODE))?,
+            initial_lives: env_or("INITIAL_LIVES", INITIAL_LIVES)?,
+            joker_replenish_every: env_or("JOKER_REPLENISH_EVERY", JOKER_REPLENISH_EVERY)?,
+            max_jokers: env_or("MAX_JOKERS", MAX_JOKERS)?,
+            estimation_tie_policy: env_or("ESTIMATION_TIE_POLICY", String::from(ESTIMATION_TIE_POLICY))?,
+            default_bet: env_or("DEFAULT_BET", DEFAULT_BET)?,
+            max_missed_rounds: env_or("MAX_MISSED_ROUNDS", MAX_MISSED_ROUNDS)?,
+            bet_lock_delay_secs: env_or("BET_LOCK_DELAY_SECS", BET_LOCK_DELAY_SECS)?,
+            versus_rounding: env_or("VERSUS_ROUNDING", String::from(VERSUS_ROUNDING))?,
+            confirm_game_end: env_or("CONFIRM_GAME_END", CONFIRM_GAME_END)?,
+            kick_grace_secs: env_or("KICK_GRACE_SECS", KICK_GRACE_SECS)?,
+            safe_money: env_or("SAFE_MONEY", SAFE_MONEY)?,
+            transition_history: env_or("TRANSITION_HISTORY", TRANSITION_HISTORY)?,
+            max_money: env_or("MAX_MONEY", MAX_MONEY)?,
+            steal_amount: env_or("STEAL_AMOUNT", STEAL_AMOUNT)?,
+            announcement_max_length: env_or("ANNOUNCEMENT_MAX_LENGTH", ANNOUNCEMENT_MAX_LENGTH)?,
+            audience_poll_from_answers: env_or("AUDIENCE_POLL_FROM_ANSWERS", AUDIENCE_POLL_FROM_ANSWERS)?,
+            audience_poll_min_answers: env_or("AUDIENCE_POLL_MIN_ANSWERS", AUDIENCE_POLL_MIN_ANSWERS)?,
+            global_question_timeout_secs: env_or("GLOBAL_QUESTION_TIMEOUT_SECS", GLOBAL_QUESTION_TIMEOUT_SECS)?,
+            tiebreaker_coin_flip: env_or("TIEBREAKER_COIN_FLIP", TIEBREAKER_COIN_FLIP)?,
+            open_betting: env_or("OPEN_BETTING", OPEN_BETTING)?,
+            time_budget_ms: env_or("TIME_BUDGET_MS", TIME_BUDGET_MS)?,
+            request_log: env_or("REQUEST_LOG", REQUEST_LOG)?,
+            reveal_mode: env_or("REVEAL_MODE", String::from(REVEAL_MODE))?,
+            category_multipliers,
+            allowed_bets,
+            bonus_wheel,
+            streak_bonus,
+            state_file: env::var("STATE_FILE").ok().filter(|state_file| !state_file.is_empty()),
+            rng_seed: env::var("RNG_SEED").ok().filter(|seed| !seed.is_empty())
+                .map(|seed| seed.trim().parse().map_err(|_| format!("RNG_SEED has the invalid value \"{}\"!", seed))).transpose()?,
+            admin_token: env::var("ADMIN_TOKEN").ok().filter(|admin_token| !admin_token.is_empty()),
+        };
+        config.validate()?;
+        Ok(config)
+    }
+    
+    //check the configuration for invalid or contradicting values
+    fn validate(&self) -> Result<(), String>
+    {
+        if self.normal_q_money < 0 || self.estimation_q_money < 0 || self.multi_select_q_money < 0 || self.bonus_fastest_money < 0 ||
+            self.steal_amount < 0 || self.default_bet < 0
+        {
+            return Err(String::from("NORMAL_Q_MONEY, ESTIMATION_Q_MONEY, MULTI_SELECT_Q_MONEY, BONUS_FASTEST_MONEY, STEAL_AMOUNT and DEFAULT_BET must be >= 0!"));
+        }
+        if self.money_floor > self.max_money || self.initial_money < self.money_floor || self.initial_money > self.max_money
+        {
+            return Err(String::from("MONEY_FLOOR <= INITIAL_MONEY <= MAX_MONEY is needed!"));
+        }
+        if !self.comeback_multiplier.is_finite() || self.comeback_multiplier < 0.0 ||
+            self.category_multipliers.values().any(|multiplier| !multiplier.is_finite() || *multiplier < 0.0) ||
+            self.bonus_wheel.iter().any(|multiplier| !multiplier.is_finite() || *multiplier < 0.0)
+        {
+            return Err(String::from("COMEBACK_MULTIPLIER, CATEGORY_MULTIPLIERS and BONUS_WHEEL must be finite numbers >= 0!"));
+        }
+        if self.allowed_bets.iter().any(|bet| *bet < 1)
+        {
+            return Err(String::from("ALLOWED_BETS must be >= 1!"));
+        }
+        if self.streak_bonus.iter().any(|(streak, bonus)| *streak < 1 || *bonus < 0)
+        {
+            return Err(String::from("STREAK_BONUS needs streaks >= 1 and bonuses >= 0!"));
+        }
+        if self.versus_max_targets < 1 || self.save_interval_ms < 1 || self.announcement_max_length < 1
+        {
+            return Err(String::from("VERSUS_MAX_TARGETS, SAVE_INTERVAL_MS and ANNOUNCEMENT_MAX_LENGTH must be >= 1!"));
+        }
+        let choices = [("GAME_MODE", &self.game_mode, &["Money", "Lives"][..]),
+            ("ESTIMATION_TIE_POLICY", &self.estimation_tie_policy, &["AllGetFull", "SplitEqually"][..]),
+            ("VERSUS_ROUNDING", &self.versus_rounding, &["Truncate", "Round", "Ceil"][..]),
+            ("REVEAL_MODE", &self.reveal_mode, &["AllAtOnce", "OneByOne"][..])];
+        for (name, value, allowed) in choices.iter()
+        {
+            if !allowed.contains(&value.as_str())
             {
-                event_id = last.unwrap().id + 1;
+                return Err(format!("{} must be one of {}!", name, allowed.join(", ")));
             }
-            let event_data = EventShowResults { correct_answer: correct_answer, previous_player_data: previous_player_data, player_data: player_data };
-            let new_event = Event { id: event_id, event_name: String::from("ShowResults"),
-                event: EventType::ShowResults(event_data) };
-            (*events).push(new_event);
-            //set new question state
-            *question_state = QuestionState::Results(false);
-        },
-        QuestionState::BettingQAnswering(true) => { //transition to results state
-            //gather necessary data
-            let question_id = data.current_question.load(Ordering::Relaxed);
-            let questions = data.questions.read().await;
-            let correct_answer = (*questions)[question_id - 1].correct_answer;
-            //compute the new money of each player
-            let mut access = data.player_data.write().await;
-            let previous_player_data = (*access).clone();
-            for player in (*access).iter_mut()
-            {
-                if player.answer == correct_answer
-                {
-                    player.money += player.money_bet;
-                }
-                else
-                {
-                    player.money -= player.money_bet;
-                    //if player has no money, give 1€ to allow continuing the game
-                    if player.money == 0
-                    {
-                        player.money = 1;
-                    }
-                }
-            }
-            let player_data = (*access).clone();
-            //create event
-            let mut events = data.game_events.write().await;
-            let mut event_id = 0;
-            let last = (*events).last();
-            if last.is_some()
-            {
-                event_id = last.unwrap().id + 1;
-            }
-            let event_data = EventShowResults { correct_answer: correct_answer, previous_player_data: previous_player_data, player_data: player_data };
-            let new_event = Event { id: event_id, event_name: String::from("ShowResults"),
-                event: EventType::ShowResults(event_data) };
-            (*events).push(new_event);
-            //set new question state
-            *question_state = QuestionState::Results(false);
-        },
-        QuestionState::EstimationQAnswering(true) => { //transition to results state
-            //gather necessary data
-            let question_id = data.current_question.load(Ordering::Relaxed);
-            let questions = data.questions.read().await;
-            let correct_answer = (*questions)[question_id - 1].correct_answer;
-            //compute the new money of each player
-            let mut closest_players = Vec::new();
-            let mut min_dinstance = usize::MAX;
-            let mut access = data.player_data.write().await;
-            let previous_player_data = (*access).clone();
-            for player in (*access).iter()
-            {
-                let diff = if player.answer >= correct_answer { player.answer - correct_answer } else { correct_answer - player.answer };
-                if diff < min_dinstance
-                {
-                    min_dinstance = diff;
-                    closest_players = vec![player.name.clone()];
-                }
-                else if diff == min_dinstance
-                {
-                    closest_players.push(player.name.clone());
-                }
-            }
-            for player in (*access).iter_mut()
-            {
-                if closest_players.iter().any(|name| name == &player.name)
-                {
-                    let estimation_q_money = env::var("ESTIMATION_Q_MONEY").unwrap_or_default().parse().unwrap_or(ESTIMATION_Q_MONEY);
-                    player.money += estimation_q_money;
-                }
-            }
-            let player_data = (*access).clone();
-            //create event
-            let mut events = data.game_events.write().await;
-            let mut event_id = 0;
-            let last = (*events).last();
-            if last.is_some()
-            {
-                event_id = last.unwrap().id + 1;
-            }
-            let event_data = EventShowResults { correct_answer: correct_answer, previous_player_data: previous_player_data, player_data: player_data };
-            let new_event = Event { id: event_id, event_name: String::from("ShowResults"),
-                event: EventType::ShowResults(event_data) };
-            (*events).push(new_event);
-            //set new question state
-            *question_state = QuestionState::Results(false);
-        },
-        QuestionState::VersusQAnswering(true) => { //transition to results state
-            //gather necessary data
-            let question_id = data.current_question.load(Ordering::Relaxed);
-            let questions = data.questions.read().await;
-            let correct_answer = (*questions)[question_id - 1].correct_answer;
-            //compute the new money of each player
-            let mut access = data.player_data.write().await;
-            let previous_player_data = (*access).clone();
-            let num_players = (*access).len();
-            let mut player_factors: Vec<f64> = vec![1.0; num_players];
-            for i in 0 .. num_players
-            {
-                if (*access)[i].vs_player.is_empty() { continue; }
-                for j in 0 .. num_players
-                {
-                    if (*access)[i].vs_player == (*access)[j].name
-                    {
-                        if (*access)[i].answer == correct_answer
-                        {
-                            //player_factors[i] *= 2.0;
-                            player_factors[j] /= 2.0;
-                        }
-                        else
-                        {
-                            //player_factors[i] /= 2.0;
-                            player_factors[j] *= 2.0;
-                        }
-                        break;
-                    }
-                }
-            }
-            for i in 0 .. num_players
-            {
-                (*access)[i].money = ((*access)[i].money as f64 * player_factors[i]) as i64;
-                //if player has no money, give 1€ to allow continuing the game
-                if (*access)[i].money == 0
-                {
-                    (*access)[i].money = 1;
-                }
-            }
-            let player_data = (*access).clone();
-            //create event
-            let mut events = data.game_events.write().await;
-            let mut event_id = 0;
-            let last = (*events).last();
-            if last.is_some()
-            {
-                event_id = last.unwrap().id + 1;
-            }
-            let event_data = EventShowResults { correct_answer: correct_answer, previous_player_data: previous_player_data, player_data: player_data };
-            let new_event = Event { id: event_id, event_name: String::from("ShowResults"),
-                event: EventType::ShowResults(event_data) };
-            (*events).push(new_event);
-            //set new question state
-            *question_state = QuestionState::Results(false);
-        },
-        _ => {},
+        }
+        Ok(())
     }
 }
 
+//clamp the money at the configured money floor and MAX_MONEY
+fn apply_money_limits(config: &Config, money: i64) -> i64
+{
+    if money < config.money_floor { config.money_floor } else if money > config.max_money { config.max_money } else { money }
+}
 
-//index site of API as information for me, which API interfaces are available. should not be visible not for users!
-#[get("/api/")]
-async fn index() -> impl Responder
+//clamp a loss in betting and versus questions at the configured SAFE_MONEY (or the previous money if that was less already)
+fn apply_safe_money(config: &Config, previous_money: i64, money: i64) -> i64
 {
-    NamedFile::open("API-Overview.htm")
+    money.max(previous_money.min(config.safe_money))
 }
 
-//join / register new player; struct for accepting the GET parameters
-#[derive(Serialize, Deserialize)]
-struct JoinPlayerData
+//apply the reward multiplier of the question's category, configured by CATEGORY_MULTIPLIERS
+fn category_reward(config: &Config, reward: i64, category: &str) -> i64
 {
-    name: String,
+    let multiplier = config.category_multipliers.get(category).copied().unwrap_or(1.0);
+    (reward as f64 * multiplier) as i64
 }
-#[get("/api/joinPlayer")]
-async fn join_player(data: web::Data<GameshowData>, params: web::Query<JoinPlayerData>) -> impl Responder
+
+//apply the comeback multiplier to the reward if the player is (one of) the trailing player(s),
+//which requires to be behind the leader (all players are level at the start)
+fn comeback_reward(config: &Config, reward: i64, money: i64, (min_money, max_money): (i64, i64)) -> i64
 {
-    let trimmed_name = String::from(params.name.trim());
-    if trimmed_name == ""
+    if money > min_money || money >= max_money
     {
-        return HttpResponse::BadRequest().body("Empty name is not allowed!");
-    }
-
-    let mut access = data.player_data.write().await;
-    if (*access).iter().all(|s| &s.name != &params.name)
-    { //only append player if it is not contained already
-        let initial_money = env::var("INITIAL_MONEY").unwrap_or_default().parse().unwrap_or(INITIAL_MONEY);
-        let initial_jokers = env::var("INITIAL_JOKERS").unwrap_or_default().parse().unwrap_or(INITIAL_JOKERS);
-        let new_player = PlayerData { name: trimmed_name.clone(), jokers: initial_jokers, money: initial_money,
-            money_bet: 0, vs_player: "".to_owned(), answer: 0 };
-        (*access).push(new_player);
+        return reward;
     }
-
-    HttpResponse::Ok().body(trimmed_name)
+    (reward as f64 * config.comeback_multiplier) as i64
 }
 
-//list all registered players' data (also given answers leaked!)
-#[get("/api/getPlayerData")]
-async fn get_player_data(data: web::Data<GameshowData>) -> impl Responder
+//round the money computed in versus questions as configured by VERSUS_ROUNDING
+fn versus_rounding(config: &Config, money: f64) -> i64
 {
-    let access = data.player_data.read().await;
-    let player_data = (*access).clone();
-
-    HttpResponse::Ok().json(player_data)
+    match config.versus_rounding.as_str()
+    {
+        "Round" => money.round() as i64,
+        "Ceil" => money.ceil() as i64,
+        _ => money as i64,
+    }
 }
 
-//accept the bets, but only when in betting question state
-#[derive(Serialize, Deserialize)]
-struct BetMoneyData
+//get the least and the most money of all players (to find the trailing players)
+fn money_range(player_data: &[PlayerData]) -> (i64, i64)
 {
-    name: String,
-    money_bet: i64,
+    let min_money = player_data.iter().map(|player| player.money).min().unwrap_or(0);
+    let max_money = player_data.iter().map(|player| player.money).max().unwrap_or(0);
+    (min_money, max_money)
 }
-#[get("/api/betMoney")]
-async fn bet_money(data: web::Data<GameshowData>, params: web::Query<BetMoneyData>) -> impl Responder
+
+//player data for the public, the versus selections and bets are hidden until the results to not influence the players
+//(the bets only until answering with OPEN_BETTING)
+fn public_player_data(config: &Config, player_data: &[PlayerData], question_state: QuestionState) -> Vec<PlayerData>
 {
-    //ensure current question state is betting, else return not acceptable
+    let mut player_data = player_data.to_vec();
+    if matches!(question_state, QuestionState::VersusQSelecting(_) | QuestionState::VersusQAnswering(_))
     {
-        let question_state = data.current_question_state.read().await;
-        if *question_state != QuestionState::BettingQBetting(false)
+        for player in player_data.iter_mut()
         {
-            return HttpResponse::NotAcceptable().body("QuestionState is not Betting(false)!");
+            player.vs_player = "".to_owned();
+            player.vs_players = Vec::new();
         }
     }
-    
-    //perform money betting
+    let bets_hidden = match question_state
     {
-        let mut name_found = false;
-        let mut access = data.player_data.write().await;
-        
-        for player in (*access).iter_mut()
+        QuestionState::BettingQBetting(_) => true,
+        QuestionState::BettingQAnswering(_) => !config.open_betting,
+        _ => false,
+    };
+    if bets_hidden
+    {
+        for player in player_data.iter_mut()
         {
-            if player.name == params.name
-            {
-                if params.money_bet < 1 || player.money < params.money_bet
-                {
-                    return HttpResponse::BadRequest().body("money_bet is invalid (< 1 or > player money)!");
-                }
-                else
-                { //set player's money_bet
-                    player.money_bet = params.money_bet;
-                    name_found = true;
-                    break;
-                }
-            }
+            player.money_bet = 0;
         }
-        if !name_found
+    }
+    player_data
+}
+
+//build the index of each player in player_data by name (needed after removing players)
+fn build_player_index(player_data: &[PlayerData]) -> HashMap<String, usize>
+{
+    player_data.iter().enumerate().map(|(i, player)| (player.name.clone(), i)).collect()
+}
+
+//current server time in milliseconds since the unix epoch, to let clients compute their clock offset
+fn unix_time_ms() -> u64
+{
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_millis() as u64).unwrap_or(0)
+}
+
+//add a new event with the next event ID
+fn push_event(events: &mut Vec<Event>, event_name: &str, event: EventType)
+{
+    let event_id = match events.last()
+    {
+        Some(last) => last.id + 1,
+        None => 0,
+    };
+    events.push(Event { id: event_id, event_name: String::from(event_name), event });
+}
+
+//add an event for every player whose money was clamped to the money floor
+fn push_player_on_floor_events(events: &mut Vec<Event>, floored_players: Vec<String>)
+{
+    for name in floored_players
+    {
+        push_event(events, "PlayerOnFloor", EventType::PlayerOnFloor(EventPlayerOnFloor { name }));
+    }
+}
+
+//create the results event; the players' answers are left out if they are revealed one by one by the host
+fn show_results_event(config: &Config, question_id: usize, question: &Question, mut previous_player_data: Vec<PlayerData>, mut player_data: Vec<PlayerData>) -> EventType
+{
+    if config.reveal_mode == "OneByOne"
+    {
+        for player in previous_player_data.iter_mut().chain(player_data.iter_mut())
         {
-            return HttpResponse::BadRequest().body("Player name was not found!");
+            player.answer = 0;
+            player.answers_selected = Vec::new();
+            player.estimate_f = None;
         }
     }
-    
-    //check if all players have bet to indicate abilitiy to proceed
-    let mut all_bet = true;
+    let correct_answer_f = if question.question_type == QuestionType::EstimationQuestion
+        { Some(question.correct_answer_f.unwrap_or(question.correct_answer as f64)) } else { None };
+    EventType::ShowResults(EventShowResults { question_index: question_id, correct_answer: question.correct_answer, correct_answers: question.correct_answers.clone(),
+        correct_answer_f, explanation: question.explanation.clone(), fun_fact: question.fun_fact.clone(),
+        previous_player_data: previous_player_data, player_data: player_data })
+}
+
+//create the compact summary of the round's results from the player data before and after scoring
+fn round_summary_event(question_id: usize, question: &Question, previous_player_data: &[PlayerData], player_data: &[PlayerData]) -> EventType
+{
+    let gains: Vec<(String, i64)> = previous_player_data.iter().zip(player_data.iter())
+        .map(|(previous, player)| (player.name.clone(), player.money - previous.money))
+        .filter(|(_, gain)| *gain > 0).collect();
+    let biggest_gain = gains.iter().max_by_key(|(_, gain)| *gain).cloned();
+    let winners = gains.into_iter().map(|(name, _)| name).collect();
+    EventType::RoundSummary(EventRoundSummary { question_index: question_id, correct_answer: question.correct_answer,
+        winners, biggest_gain })
+}
+
+//create the game ending event with the winners (disabled players do not win, in GAME_MODE Lives the most lives count first)
+fn game_ending_event(config: &Config, player_data: &[PlayerData]) -> EventGameEnding
+{
+    let candidates: Vec<&PlayerData> = player_data.iter().filter(|player| !player.disabled).collect();
+    let most_lives = candidates.iter().map(|player| player.lives).max().unwrap_or(0);
+    let candidates: Vec<&PlayerData> = candidates.into_iter().filter(|player| !lives_mode(config) || player.lives == most_lives).collect();
+    let winning_money = candidates.iter().map(|player| player.money).max().unwrap_or(0);
+    let winners = candidates.into_iter().filter(|player| player.money == winning_money)
+        .map(|player| player.name.clone()).collect();
+    EventGameEnding { player_data: player_data.to_vec(), winners, winning_money }
+}
+
+//add the game ending event, with TIEBREAKER_COIN_FLIP a tie of the winners is resolved by a coin flip afterwards
+async fn push_game_ending(data: &GameshowData, events: &mut Vec<Event>, player_data: &[PlayerData])
+{
+    let event_data = game_ending_event(&data.config, player_data);
+    let winners = event_data.winners.clone();
+    push_event(events, "GameEnding", EventType::GameEnding(event_data));
+    let tiebreaker_coin_flip = data.config.tiebreaker_coin_flip;
+    if tiebreaker_coin_flip && winners.len() > 1
     {
-        let access = data.player_data.read().await;
-        for player in (*access).iter()
+        let mut rng = data.rng.lock().await;
+        if let Some(winner) = winners.choose(&mut *rng)
         {
-            if player.money_bet < 1
-            {
-                all_bet = false;
-                break;
-            }
+            let event_data = EventTiebreakerResolved { winner: winner.clone(), method: String::from("CoinFlip") };
+            push_event(events, "TiebreakerResolved", EventType::TiebreakerResolved(event_data));
         }
     }
-    if all_bet
+}
+
+//check if the game ends instead of beginning the question with the given number
+//(no questions left, QUESTION_LIMIT reached or at most one player left in GAME_MODE Lives)
+async fn is_game_over(data: &GameshowData, question_id: usize, num_questions: usize) -> bool
+{
+    let question_limit = data.config.question_limit;
+    let limit_reached = question_limit > 0 && data.questions_played.load(Ordering::Relaxed) >= question_limit;
+    let last_player_left = {
+        let access = data.player_data.read().await;
+        lives_mode(&data.config) && (*access).len() > 1 && (*access).iter().filter(|player| !is_eliminated(&data.config, player)).count() <= 1
+    };
+    question_id > num_questions || limit_reached || last_player_left
+}
+
+//check if the player answered the question correctly (estimation questions have no correct answerers, only closest ones)
+fn answered_correctly(question: &Question, player: &PlayerData) -> bool
+{
+    match question.question_type
     {
-        let mut question_state = data.current_question_state.write().await;
-        *question_state = QuestionState::BettingQBetting(true);
+        QuestionType::EstimationQuestion => false,
+        QuestionType::MultiSelectQuestion => !question.correct_answers.is_empty() &&
+            player.answers_selected.len() == question.correct_answers.len() &&
+            player.answers_selected.iter().all(|answer| question.correct_answers.contains(answer)),
+        _ => is_correct_answer(question, player.answer),
     }
-    
-    HttpResponse::Ok().finish()
 }
 
-//accept the versus selection, but only when in selecting question state
-#[derive(Serialize, Deserialize)]
-struct AttackPlayerData
+//check if the player gave any kind of answer for the current question
+fn has_answered(player: &PlayerData) -> bool
 {
-    name: String,
-    vs_player: String,
+    player.answer >= 1 || !player.answers_selected.is_empty() || player.estimate_f.is_some()
 }
-#[get("/api/attackPlayer")]
-async fn attack_player(data: web::Data<GameshowData>, params: web::Query<AttackPlayerData>) -> impl Responder
+
+//check if the game is played with lives instead of money
+fn lives_mode(config: &Config) -> bool
 {
-    //ensure current question state is selecting, else return not acceptable
+    config.game_mode == "Lives"
+}
+
+//check if the player has no lives left in GAME_MODE Lives (eliminated players are ignored when waiting for everyone)
+fn is_eliminated(config: &Config, player: &PlayerData) -> bool
+{
+    lives_mode(config) && player.lives == 0
+}
+
+//check if the player takes part in the current question (disabled, eliminated, soft kicked players and players without time budget are not waited for)
+fn participates_this_round(config: &Config, player: &PlayerData) -> bool
+{
+    !player.disabled && !is_eliminated(config, player) && player.pending_removal.is_none() && player.time_budget_ms != Some(0)
+}
+
+//check if all participating players have acted in the current betting, selecting or answering state (and there is anyone participating),
+//single source of truth for the handlers and the background ticker
+fn is_round_ready(config: &Config, player_data: &[PlayerData], question_state: QuestionState) -> bool
+{
+    let has_acted: fn(&PlayerData) -> bool = match question_state
     {
-        let question_state = data.current_question_state.read().await;
-        if *question_state != QuestionState::VersusQSelecting(false)
-        {
-            return HttpResponse::NotAcceptable().body("QuestionState is not VersusQSelecting(false)!");
-        }
+        QuestionState::BettingQBetting(false) => |player| player.money_bet >= 1,
+        QuestionState::VersusQSelecting(false) => |player| !player.vs_player.is_empty(),
+        QuestionState::NormalQAnswering(false) | QuestionState::BettingQAnswering(false) | QuestionState::EstimationQAnswering(false) |
+        QuestionState::VersusQAnswering(false) | QuestionState::MultiSelectQAnswering(false) => has_answered,
+        _ => { return false; },
+    };
+    let mut participants = player_data.iter().filter(|player| participates_this_round(config, player)).peekable();
+    participants.peek().is_some() && participants.all(has_acted)
+}
+
+//mark the round as ready once everyone acted, answers only after the ANSWER_GRACE_SECS (then done in check_state_add_events)
+async fn set_round_ready(data: &GameshowData, question_state: &mut QuestionState)
+{
+    match *question_state
+    {
+        QuestionState::BettingQBetting(false) => { *question_state = QuestionState::BettingQBetting(true); },
+        QuestionState::VersusQSelecting(false) => { *question_state = QuestionState::VersusQSelecting(true); },
+        _ => {
+            let answer_grace_secs = data.config.answer_grace_secs;
+            if answer_grace_secs == 0
+            {
+                set_answering_ready(question_state);
+            }
+            else
+            { //start the grace period, the transition is done in check_state_add_events once it is over
+                let mut ready_at = data.answers_ready_at.lock().await;
+                if ready_at.is_none()
+                {
+                    *ready_at = Some(Instant::now());
+                }
+            }
+        },
     }
-    
-    //perform selecting
+}
+
+//in GAME_MODE Lives the money stays as before, instead every remaining player without a correct answer
+//(detected by the unchanged correct_count) loses a life
+fn apply_lives(config: &Config, previous_player_data: &[PlayerData], player_data: &mut [PlayerData])
+{
+    if !lives_mode(config)
     {
-        if params.name == params.vs_player
-        {
-            return HttpResponse::BadRequest().body("name and vs_player are equal!");
-        }
-        
-        let mut access = data.player_data.write().await;
-        if !(*access).iter().any(|player| &player.name == &params.name)
-        {
-            return HttpResponse::BadRequest().body("Player name was not found!");
-        }
-        if !(*access).iter().any(|player| &player.name == &params.vs_player)
-        {
-            return HttpResponse::BadRequest().body("Player vs_player was not found!");
-        }
-        
-        for player in (*access).iter_mut()
+        return;
+    }
+    let elimination_order = player_data.iter().filter_map(|player| player.elimination_order).max().unwrap_or(0) + 1;
+    for (previous, player) in previous_player_data.iter().zip(player_data.iter_mut())
+    {
+        player.money = previous.money;
+        //players not taking part in the question (e.g. disabled ones) do not lose a life
+        if previous.lives > 0 && participates_this_round(config, previous) && player.correct_count == previous.correct_count
         {
-            if player.name == params.name
-            { //set player's selection
-                player.vs_player = params.vs_player.clone();
-                break;
+            player.lives -= 1;
+            if player.lives == 0
+            { //remember when the player went out for the final standing
+                player.eliminated = true;
+                player.elimination_order = Some(elimination_order);
             }
         }
     }
-    
-    //check if all players have selected to indicate abilitiy to proceed
-    let mut all_selected = true;
+}
+
+//update the player's answer statistics and streak when scoring a question, reaching a STREAK_BONUS milestone gives its bonus money
+//(players not taking part in the question keep their statistics and streak)
+fn count_answer(config: &Config, player: &mut PlayerData, correct: bool)
+{
+    if !participates_this_round(config, player)
     {
-        let access = data.player_data.read().await;
-        for player in (*access).iter()
+        return;
+    }
+    let correct = correct && has_answered(player);
+    if has_answered(player)
+    {
+        player.answered_count += 1;
+    }
+    if correct
+    {
+        player.correct_count += 1;
+        player.streak += 1;
+        if let Some(&(_, bonus)) = config.streak_bonus.iter().find(|(streak, _)| *streak == player.streak)
         {
-            if player.vs_player == ""
-            {
-                all_selected = false;
-                break;
-            }
+            player.money = apply_money_limits(config, player.money.saturating_add(bonus));
         }
     }
-    if all_selected
+    else
     {
-        let mut question_state = data.current_question_state.write().await;
-        *question_state = QuestionState::VersusQSelecting(true);
+        player.streak = 0;
     }
-    
-    HttpResponse::Ok().finish()
 }
 
-//accept the question answer
-#[derive(Serialize, Deserialize)]
-struct AnswerQuestionData
+//compute the money won with the selected answers of a multi-select question:
+//full reward for an exact match, else a share for each correct pick minus wrong picks (at least zero)
+fn multi_select_reward(answers_selected: &[usize], correct_answers: &[usize], reward: i64) -> i64
 {
-    name: String,
-    answer: usize,
+    if correct_answers.is_empty()
+    {
+        return 0;
+    }
+    let correct_picks = answers_selected.iter().filter(|answer| correct_answers.contains(answer)).count() as i64;
+    let wrong_picks = answers_selected.len() as i64 - correct_picks;
+    if correct_picks == correct_answers.len() as i64 && wrong_picks == 0
+    {
+        return reward;
+    }
+    let net_picks = correct_picks - wrong_picks;
+    if net_picks <= 0 { 0 } else { reward * net_picks / correct_answers.len() as i64 }
 }
-#[get("/api/answerQuestion")]
-async fn answer_question(data: web::Data<GameshowData>, params: web::Query<AnswerQuestionData>) -> impl Responder
+
+//push the results and the round summary (in REVEAL_MODE OneByOne the summary follows the last revealPlayer)
+fn push_results_events(config: &Config, events: &mut Vec<Event>, question_id: usize, question: &Question, previous_player_data: Vec<PlayerData>, player_data: Vec<PlayerData>)
 {
-    //ensure current question state is answering, else return not acceptable
+    let round_summary = round_summary_event(question_id, question, &previous_player_data, &player_data);
+    push_event(events, "ShowResults", show_results_event(config, question_id, question, previous_player_data, player_data));
+    if config.reveal_mode != "OneByOne"
+    {
+        push_event(events, "RoundSummary", round_summary);
+    }
+}
+
+//the players whose answers are still hidden in the current results (REVEAL_MODE OneByOne, until revealPlayer)
+fn unrevealed_players(config: &Config, events: &[Event], question_state: QuestionState) -> Vec<String>
+{
+    if config.reveal_mode != "OneByOne" || !matches!(question_state, QuestionState::Results(_))
+    {
+        return Vec::new();
+    }
+    let position = match events.iter().rposition(|event| event.event_name == "ShowResults")
+    {
+        Some(position) => position,
+        None => { return Vec::new(); },
+    };
+    let revealed: Vec<&String> = events[position + 1 ..].iter().filter_map(|event| match &event.event
+    {
+        EventType::RevealPlayer(reveal) => Some(&reveal.name),
+        _ => None,
+    }).collect();
+    match &events[position].event
+    {
+        EventType::ShowResults(results) => results.player_data.iter().map(|player| player.name.clone())
+            .filter(|name| !revealed.contains(&name)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+//remove the answers of the players that were not revealed yet from the player data
+fn hide_unrevealed_answers(config: &Config, player_data: &mut [PlayerData], question_state: QuestionState, events: &[Event])
+{
+    let unrevealed = unrevealed_players(config, events, question_state);
+    for player in player_data.iter_mut().filter(|player| unrevealed.contains(&player.name))
+    {
+        player.answer = 0;
+        player.answers_selected = Vec::new();
+        player.estimate_f = None;
+    }
+}
+
+//single source of truth, which API actions are allowed in which question state (the handlers check against this)
+fn allowed_actions(question_state: QuestionState) -> Vec<&'static str>
+{
+    //actions that are always possible
+    let mut actions = vec!["joinPlayer", "getPlayerData", "getPlayerDataFull", "playerData", "getGameEvents", "event", "poll", "replay", "accuracy", "summary", "state", "allowedActions", "giveMoney", "setMoney", "setJokers", "setJokersAll", "setPlayerDisabled", "kickPlayer", "adminQuestion", "remainingQuestions", "currentQuestions", "transitions", "peekNext", "announce", "myOptions", "validateQuestions", "playerEvents"];
+    let state_actions: &[&'static str] = match question_state
+    {
+        QuestionState::Results(false) => &["activateNextQuestion", "setNextQuestion", "shuffleRemaining", "startQuestion", "loadQuestions", "revealPlayer", "bonusFastest", "spinBonus", "fixCorrectAnswer", "recomputeResults", "correctAnswer"],
+        QuestionState::Results(true) => &["correctAnswer"],
+        QuestionState::CategoryTeaser(false) => &["revealQuestion"],
+        QuestionState::NormalQAnswering(false) | QuestionState::BettingQAnswering(false) =>
+            &["answerQuestion", "getJokerFiftyFifty", "getJokerAudiencePoll", "forceQuestionResults"],
+        QuestionState::EstimationQAnswering(false) | QuestionState::VersusQAnswering(false) |
+        QuestionState::MultiSelectQAnswering(false) => &["answerQuestion", "forceQuestionResults"],
+        QuestionState::BettingQBetting(false) => &["betMoney", "forceQuestionAnswering"],
+        QuestionState::BettingQBetting(true) => &["forceQuestionAnswering"], //skips the BET_LOCK_DELAY_SECS
+        QuestionState::VersusQSelecting(false) => &["attackPlayer", "forceQuestionAnswering"],
+        QuestionState::AwaitingGameEnd => &["confirmGameEnd", "fixCorrectAnswer", "correctAnswer"],
+        QuestionState::GameEnding => &["newGame", "setNextQuestion", "shuffleRemaining", "startQuestion", "loadQuestions", "fixCorrectAnswer", "correctAnswer"],
+        _ => &[],
+    };
+    actions.extend_from_slice(state_actions);
+    if question_state != QuestionState::GameEnding
+    {
+        actions.push("endGame");
+    }
+    actions
+}
+
+//get the name of the question state and whether it is ready to transition to the next state
+fn question_state_info(question_state: QuestionState) -> (&'static str, bool)
+{
+    match question_state
+    {
+        QuestionState::Results(ready) => ("Results", ready),
+        QuestionState::CategoryTeaser(ready) => ("CategoryTeaser", ready),
+        QuestionState::NormalQAnswering(ready) => ("NormalQAnswering", ready),
+        QuestionState::BettingQBetting(ready) => ("BettingQBetting", ready),
+        QuestionState::BettingQAnswering(ready) => ("BettingQAnswering", ready),
+        QuestionState::EstimationQAnswering(ready) => ("EstimationQAnswering", ready),
+        QuestionState::VersusQSelecting(ready) => ("VersusQSelecting", ready),
+        QuestionState::VersusQAnswering(ready) => ("VersusQAnswering", ready),
+        QuestionState::MultiSelectQAnswering(ready) => ("MultiSelectQAnswering", ready),
+        QuestionState::AwaitingGameEnd => ("AwaitingGameEnd", false),
+        QuestionState::GameEnding => ("GameEnding", false),
+    }
+}
+
+//check if the API action is allowed in the question state
+fn is_action_allowed(question_state: QuestionState, action: &str) -> bool
+{
+    allowed_actions(question_state).contains(&action)
+}
+
+//set the answering question state to be ready for transition to results
+fn set_answering_ready(question_state: &mut QuestionState)
+{
+    match *question_state
+    {
+        QuestionState::NormalQAnswering(_) => { *question_state = QuestionState::NormalQAnswering(true); },
+        QuestionState::BettingQAnswering(_) => { *question_state = QuestionState::BettingQAnswering(true); },
+        QuestionState::EstimationQAnswering(_) => { *question_state = QuestionState::EstimationQAnswering(true); },
+        QuestionState::VersusQAnswering(_) => { *question_state = QuestionState::VersusQAnswering(true); },
+        QuestionState::MultiSelectQAnswering(_) => { *question_state = QuestionState::MultiSelectQAnswering(true); },
+        _ => {},
+    }
+}
+
+//depending on question type begin different question-specific event, returns the new question state
+fn begin_question(events: &mut Vec<Event>, question_id: usize, question_type: QuestionType, category: String, question: String,
+    answers: Vec<String>) -> QuestionState
+{
+    match question_type
+    {
+        QuestionType::NormalQuestion => {
+            let event_data = EventBeginNormalQAnswering { question_type: question_type, current_question: question_id,
+                category: category, question: question, answers: answers };
+            push_event(events, "BeginNormalQAnswering", EventType::BeginNormalQAnswering(event_data));
+            QuestionState::NormalQAnswering(false)
+        },
+        QuestionType::BettingQuestion => {
+            let event_data = EventBeginBettingQBetting { question_type: question_type, current_question: question_id, category: category };
+            push_event(events, "BeginBettingQBetting", EventType::BeginBettingQBetting(event_data));
+            QuestionState::BettingQBetting(false)
+        },
+        QuestionType::EstimationQuestion => {
+            let event_data = EventBeginEstimationQAnswering { question_type: question_type, current_question: question_id, category: category,
+                question: question };
+            push_event(events, "BeginEstimationQAnswering", EventType::BeginEstimationQAnswering(event_data));
+            QuestionState::EstimationQAnswering(false)
+        },
+        QuestionType::VersusQuestion => {
+            let event_data = EventBeginVersusQSelecting { question_type, current_question: question_id, category };
+            push_event(events, "BeginVersusQSelecting", EventType::BeginVersusQSelecting(event_data));
+            QuestionState::VersusQSelecting(false)
+        },
+        QuestionType::MultiSelectQuestion => {
+            let event_data = EventBeginMultiSelectQAnswering { question_type, current_question: question_id,
+                category, question, answers };
+            push_event(events, "BeginMultiSelectQAnswering", EventType::BeginMultiSelectQAnswering(event_data));
+            QuestionState::MultiSelectQAnswering(false)
+        },
+    }
+}
+
+//check if next question state is possible/initiated and transition
+//(by preparing everything and adding an event)
+//the current_question_state write lock is held for the whole check, so concurrent calls from the ticker and handlers (on any worker)
+//are serialized and a transition can never happen twice: the second call already sees the new state
+async fn check_state_add_events(data: web::Data<GameshowData>)
+{
+    let config = &data.config;
+    let mut question_state = data.current_question_state.write().await;
+    let previous_question_state = *question_state;
+    //players who did not answer within their remaining time budget are skipped from now on
+    if config.time_budget_ms > 0 && is_action_allowed(*question_state, "answerQuestion")
+    {
+        exhaust_time_budgets(&data).await;
+    }
+    //players may have left or been disabled since the last action, so the round can be ready without a new action
+    let round_ready = is_round_ready(config, &data.player_data.read().await, *question_state);
+    if round_ready
+    {
+        set_round_ready(&data, &mut question_state).await;
+    }
+    //force the end of betting, selecting and answering after the question's or the global timeout, so that no round can hang forever
+    let question_timeout_secs = question_timeout_secs(&data, *question_state).await;
+    if question_timeout_secs > 0 && (is_action_allowed(*question_state, "forceQuestionAnswering") ||
+        is_action_allowed(*question_state, "forceQuestionResults"))
+    {
+        let state_began_at = *data.state_began_at.lock().await;
+        if state_began_at.elapsed() >= Duration::from_secs(question_timeout_secs)
+        {
+            force_ready(&data, &mut question_state).await;
+        }
+    }
+    //finish the answer grace period if it is over (or drop it if the state was changed already)
+    {
+        let mut ready_at = data.answers_ready_at.lock().await;
+        if let Some(time) = *ready_at
+        {
+            let answer_grace_secs = config.answer_grace_secs;
+            if !is_action_allowed(*question_state, "answerQuestion")
+            {
+                *ready_at = None;
+            }
+            else if time.elapsed() >= Duration::from_secs(answer_grace_secs)
+            {
+                set_answering_ready(&mut question_state);
+                *ready_at = None;
+            }
+        }
+    }
+    //automatically start the game once enough players joined (only once at the game's start)
+    let auto_start = config.auto_start;
+    if auto_start && *question_state == QuestionState::Results(false) && !data.auto_started.load(Ordering::Relaxed) &&
+        data.current_question.load(Ordering::Relaxed) == 0
+    {
+        let min_players = config.min_players;
+        let num_questions = data.questions.read().await.len();
+        let num_players = data.player_data.read().await.len();
+        if num_questions > 0 && num_players >= min_players
+        {
+            data.auto_started.store(true, Ordering::Relaxed);
+            *question_state = QuestionState::Results(true);
+        }
+    }
+    match *question_state
+    {
+        QuestionState::Results(true) => { //transition to next question (different states for different questions)
+            //reset the bet lock of the last question first, the lock order requires it before the questions and player data
+            *data.bets_locked_until.lock().await = None;
+            //gather necessary data
+            let question_id = data.current_question.fetch_add(1, Ordering::Relaxed) + 1;
+            let questions = data.questions.read().await;
+            let num_questions = (*questions).len();
+            if is_game_over(&data, question_id, num_questions).await
+            { //game ending
+                if config.confirm_game_end
+                { //wait for the host to confirm the game end
+                    let mut events = data.write_events().await;
+                    let event_data = EventReadyToEndGame { current_question: question_id - 1 };
+                    push_event(&mut events, "ReadyToEndGame", EventType::ReadyToEndGame(event_data));
+                    //set new question state
+                    *question_state = QuestionState::AwaitingGameEnd;
+                }
+                else
+                {
+                    let access = data.player_data.read().await;
+                    //create event
+                    let mut events = data.write_events().await;
+                    push_game_ending(&data, &mut events, &access).await;
+                    //set new question state
+                    *question_state = QuestionState::GameEnding;
+                }
+            }
+            else
+            { //next question
+                let questions_played = data.questions_played.fetch_add(1, Ordering::Relaxed);
+                let joker_replenish_every = config.joker_replenish_every;
+                let replenish_jokers = joker_replenish_every > 0 && questions_played > 0 && questions_played.is_multiple_of(joker_replenish_every);
+                let max_jokers = config.max_jokers;
+                let question_type = (*questions)[question_id - 1].question_type.clone();
+                let category = (*questions)[question_id - 1].category.clone();
+                let question = (*questions)[question_id - 1].question.clone();
+                let answers = (*questions)[question_id - 1].answers.clone();
+                //reset bets and question answers for all players
+                let mut player_access = data.player_data.write().await;
+                for player in (*player_access).iter_mut()
+                { //change zeros to None when using Options
+                    player.money_bet = 0;
+                    player.vs_player = "".to_owned();
+                    player.vs_players = Vec::new();
+                    player.answer = 0;
+                    player.answers_selected = Vec::new();
+                    player.estimate_f = None;
+                    player.answered_at = None;
+                    player.removed_answers = Vec::new();
+                    player.bonus_spun = false;
+                    if replenish_jokers && (max_jokers == 0 || player.jokers < max_jokers)
+                    {
+                        player.jokers += 1;
+                    }
+                }
+                data.roster_version.fetch_add(1, Ordering::Relaxed);
+                data.fastest_bonus_question.store(0, Ordering::Relaxed);
+                let mut events = data.write_events().await;
+                let category_teaser = config.category_teaser;
+                if category_teaser
+                { //only announce the category, the question begins after revealQuestion
+                    let event_data = EventCategoryTeaser { question_type, current_question: question_id, category };
+                    push_event(&mut events, "CategoryTeaser", EventType::CategoryTeaser(event_data));
+                    //set new question state
+                    *question_state = QuestionState::CategoryTeaser(false);
+                }
+                else
+                {
+                    *question_state = begin_question(&mut events, question_id, question_type, category, question, answers);
+                }
+            }
+        },
+        QuestionState::CategoryTeaser(true) => { //transition to the question after the teaser
+            //gather necessary data
+            let question_id = data.current_question.load(Ordering::Relaxed);
+            let questions = data.questions.read().await;
+            let current = &(*questions)[question_id - 1];
+            //create event and set new question state
+            let mut events = data.write_events().await;
+            *question_state = begin_question(&mut events, question_id, current.question_type, current.category.clone(),
+                current.question.clone(), current.answers.clone());
+        },
+        QuestionState::BettingQBetting(true) => { //lock the bets and transition to answering state after BET_LOCK_DELAY_SECS
+            let mut locked_until = data.bets_locked_until.lock().await;
+            if locked_until.is_none()
+            {
+                let bet_lock_delay_secs = config.bet_lock_delay_secs;
+                *locked_until = Some(Instant::now() + Duration::from_secs(bet_lock_delay_secs));
+                let access = data.player_data.read().await;
+                let num_players = (*access).iter().filter(|player| player.money_bet >= 1).count();
+                //the bets are only public with OPEN_BETTING
+                let bets = if config.open_betting
+                {
+                    Some((*access).iter().map(|player| (player.name.clone(), player.money_bet)).collect())
+                }
+                else { None };
+                let mut events = data.write_events().await;
+                push_event(&mut events, "BetsLocked", EventType::BetsLocked(EventBetsLocked { num_players, bets }));
+            }
+            if locked_until.is_some_and(|time| Instant::now() >= time)
+            {
+                *locked_until = None;
+                //gather necessary data
+                let question_id = data.current_question.load(Ordering::Relaxed);
+                let questions = data.questions.read().await;
+                let question = (*questions)[question_id - 1].question.clone();
+                let answers = (*questions)[question_id - 1].answers.clone();
+                let bets = if config.open_betting
+                {
+                    let access = data.player_data.read().await;
+                    Some((*access).iter().map(|player| (player.name.clone(), player.money_bet)).collect())
+                }
+                else { None };
+                //create event
+                let mut events = data.write_events().await;
+                let event_data = EventBeginBettingQAnswering { current_question: question_id, num_questions: (*questions).len(),
+                    question, answers, bets };
+                push_event(&mut events, "BeginBettingQAnswering", EventType::BeginBettingQAnswering(event_data));
+                //set new question state
+                *question_state = QuestionState::BettingQAnswering(false);
+            }
+        },
+        QuestionState::VersusQSelecting(true) => { //transition to answering state
+            //gather necessary data
+            let question_id = data.current_question.load(Ordering::Relaxed);
+            let questions = data.questions.read().await;
+            let question = (*questions)[question_id - 1].question.clone();
+            let answers = (*questions)[question_id - 1].answers.clone();
+            //create event
+            let mut events = data.write_events().await;
+            let event_data = EventBeginVersusQAnswering { current_question: question_id, num_questions: (*questions).len(),
+                question, answers };
+            push_event(&mut events, "BeginVersusQAnswering", EventType::BeginVersusQAnswering(event_data));
+            //set new question state
+            *question_state = QuestionState::VersusQAnswering(false);
+        },
+        QuestionState::NormalQAnswering(true) => { //transition to results state
+            //gather necessary data
+            let question_id = data.current_question.load(Ordering::Relaxed);
+            let questions = data.questions.read().await;
+            let question = &(*questions)[question_id - 1];
+            //compute the new money of each player
+            let mut access = data.player_data.write().await;
+            let previous_player_data = (*access).clone();
+            let money_range = money_range(&previous_player_data);
+            for player in (*access).iter_mut()
+            {
+                count_answer(config, player, answered_correctly(question, player));
+                if is_correct_answer(question, player.answer)
+                {
+                    let normal_q_money = config.normal_q_money;
+                    let reward = category_reward(config, normal_q_money, &question.category);
+                    player.money = apply_money_limits(config, player.money.saturating_add(comeback_reward(config, reward, player.money, money_range)));
+                }
+            }
+            //steal questions: every correct answerer takes money from a random wrong answerer (only as much as above the floor)
+            let mut floored_players = Vec::new();
+            if question.steal
+            {
+                let steal_amount = config.steal_amount;
+                let (thieves, victims): (Vec<usize>, Vec<usize>) = (0 .. (*access).len())
+                    .filter(|&i| participates_this_round(config, &(*access)[i]))
+                    .partition(|&i| is_correct_answer(question, (*access)[i].answer));
+                let mut rng = data.rng.lock().await;
+                for thief in thieves
+                {
+                    if let Some(&victim) = victims.choose(&mut *rng)
+                    {
+                        let victim_money = (*access)[victim].money;
+                        let money = victim_money.saturating_sub(steal_amount);
+                        (*access)[victim].money = apply_money_limits(config, money);
+                        if money < config.money_floor && !floored_players.contains(&(*access)[victim].name)
+                        {
+                            floored_players.push((*access)[victim].name.clone());
+                        }
+                        let stolen = (victim_money - (*access)[victim].money).max(0);
+                        (*access)[thief].money = apply_money_limits(config, (*access)[thief].money.saturating_add(stolen));
+                    }
+                }
+            }
+            apply_lives(config, &previous_player_data, &mut access);
+            data.roster_version.fetch_add(1, Ordering::Relaxed);
+            let player_data = (*access).clone();
+            //create event
+            let mut events = data.write_events().await;
+            push_results_events(config, &mut events, question_id, &(*questions)[question_id - 1], previous_player_data, player_data);
+            if !lives_mode(config)
+            {
+                push_player_on_floor_events(&mut events, floored_players);
+            }
+            //set new question state
+            *question_state = QuestionState::Results(false);
+        },
+        QuestionState::BettingQAnswering(true) => { //transition to results state
+            //gather necessary data
+            let question_id = data.current_question.load(Ordering::Relaxed);
+            let questions = data.questions.read().await;
+            let question = &(*questions)[question_id - 1];
+            //compute the new money of each player
+            let mut access = data.player_data.write().await;
+            let previous_player_data = (*access).clone();
+            let money_range = money_range(&previous_player_data);
+            let mut floored_players = Vec::new();
+            let mut clamped_bets = Vec::new();
+            for player in (*access).iter_mut()
+            {
+                count_answer(config, player, answered_correctly(question, player));
+                //defensive: never allow a bet to be bigger than the player's money, clamp to avoid a negative balance
+                if player.money_bet > player.money
+                {
+                    clamped_bets.push(EventBetClamped { name: player.name.clone(), money_bet: player.money_bet, money: player.money });
+                    player.money_bet = player.money;
+                }
+                if is_correct_answer(question, player.answer)
+                {
+                    let payout = (player.money_bet as f64 * question.bet_payout_ratio.unwrap_or(1.0)) as i64;
+                    let reward = category_reward(config, payout, &question.category);
+                    player.money = apply_money_limits(config, player.money.saturating_add(comeback_reward(config, reward, player.money, money_range)));
+                }
+                else
+                {
+                    let money = apply_safe_money(config, player.money, player.money - player.money_bet);
+                    //if player has no money, give 1€ to allow continuing the game
+                    player.money = apply_money_limits(config, money);
+                    if money < config.money_floor
+                    {
+                        floored_players.push(player.name.clone());
+                    }
+                }
+            }
+            //the fastest correct bettor gets the question's speed bonus on top
+            if let Some(bet_speed_bonus) = question.bet_speed_bonus
+            {
+                let fastest = (*access).iter_mut()
+                    .filter(|player| player.money_bet >= 1 && player.answered_at.is_some() && is_correct_answer(question, player.answer))
+                    .min_by_key(|player| player.answered_at);
+                if let Some(player) = fastest
+                {
+                    player.money = apply_money_limits(config, player.money.saturating_add(bet_speed_bonus));
+                }
+            }
+            apply_lives(config, &previous_player_data, &mut access);
+            data.roster_version.fetch_add(1, Ordering::Relaxed);
+            let player_data = (*access).clone();
+            //create events
+            let mut events = data.write_events().await;
+            push_results_events(config, &mut events, question_id, &(*questions)[question_id - 1], previous_player_data, player_data);
+            if !lives_mode(config)
+            {
+                push_player_on_floor_events(&mut events, floored_players);
+            }
+            for event_data in clamped_bets
+            {
+                push_event(&mut events, "BetClamped", EventType::BetClamped(event_data));
+            }
+            //set new question state
+            *question_state = QuestionState::Results(false);
+        },
+        QuestionState::EstimationQAnswering(true) => { //transition to results state
+            //gather necessary data
+            let question_id = data.current_question.load(Ordering::Relaxed);
+            let questions = data.questions.read().await;
+            let correct_answer = (*questions)[question_id - 1].correct_answer;
+            let correct_answer_f = (*questions)[question_id - 1].correct_answer_f;
+            //compute the new money of each player (without players, there are no closest players and the results are empty)
+            let mut closest_players = Vec::new();
+            let mut min_dinstance = f64::INFINITY;
+            let mut access = data.player_data.write().await;
+            let previous_player_data = (*access).clone();
+            //only the participating players are ranked (disabled, eliminated or soft kicked players would count with an estimate of 0)
+            for player in (*access).iter().filter(|player| participates_this_round(&data.config, player))
+            {
+                let diff = match correct_answer_f
+                {
+                    Some(correct_answer_f) => (player.estimate_f.unwrap_or(player.answer as f64) - correct_answer_f).abs(),
+                    None => (if player.answer >= correct_answer { player.answer - correct_answer } else { correct_answer - player.answer }) as f64,
+                };
+                if diff.is_nan()
+                { //can not be compared, so it can not win
+                    continue;
+                }
+                if diff < min_dinstance || closest_players.is_empty()
+                {
+                    min_dinstance = diff;
+                    closest_players = vec![player.name.clone()];
+                }
+                else if diff == min_dinstance
+                {
+                    closest_players.push(player.name.clone());
+                }
+            }
+            let money_range = money_range(&previous_player_data);
+            let split_equally = config.estimation_tie_policy == "SplitEqually";
+            for player in (*access).iter_mut().filter(|player| participates_this_round(config, player))
+            {
+                let closest = closest_players.iter().position(|name| name == &player.name);
+                count_answer(config, player, closest.is_some());
+                if let Some(position) = closest
+                {
+                    let mut estimation_q_money = config.estimation_q_money;
+                    if split_equally
+                    { //the remainder goes to the first players in the roster order, one each
+                        let num_closest = closest_players.len() as i64;
+                        let remainder = estimation_q_money % num_closest;
+                        estimation_q_money = estimation_q_money / num_closest + if (position as i64) < remainder { 1 } else { 0 };
+                    }
+                    let reward = category_reward(config, estimation_q_money, &(*questions)[question_id - 1].category);
+                    player.money = apply_money_limits(config, player.money.saturating_add(comeback_reward(config, reward, player.money, money_range)));
+                }
+            }
+            apply_lives(config, &previous_player_data, &mut access);
+            data.roster_version.fetch_add(1, Ordering::Relaxed);
+            let player_data = (*access).clone();
+            //create event
+            let mut events = data.write_events().await;
+            push_results_events(config, &mut events, question_id, &(*questions)[question_id - 1], previous_player_data, player_data);
+            //set new question state
+            *question_state = QuestionState::Results(false);
+        },
+        QuestionState::VersusQAnswering(true) => { //transition to results state
+            //gather necessary data
+            let question_id = data.current_question.load(Ordering::Relaxed);
+            let questions = data.questions.read().await;
+            let question = &(*questions)[question_id - 1];
+            //compute the new money of each player
+            let mut access = data.player_data.write().await;
+            let previous_player_data = (*access).clone();
+            let num_players = (*access).len();
+            //first pass: compute every attack's effect from the snapshot of the answers, so that the order of the players
+            //does not matter. every attack is independent: in a mutual duel both attacks are applied to the respective other
+            //player (e.g. both correct => both lose half their money, one correct => the other one loses half, the correct one doubles).
+            //with multiple targets the effect is split among them (e.g. two targets => each loses a quarter or gains half)
+            let mut adjustments: Vec<(usize, f64)> = Vec::new();
+            let mut missing_targets = Vec::new();
+            for attacker in previous_player_data.iter()
+            {
+                let num_targets = attacker.vs_players.len() as f64;
+                for vs_player in attacker.vs_players.iter()
+                {
+                    if let Some(target) = previous_player_data.iter().position(|player| &player.name == vs_player)
+                    {
+                        let factor = if is_correct_answer(question, attacker.answer) { 1.0 - 0.5 / num_targets } else { 1.0 + 1.0 / num_targets };
+                        adjustments.push((target, factor));
+                    }
+                    else
+                    { //target was kicked after the selection, the attack has no effect
+                        missing_targets.push(EventVersusTargetMissing { attacker: attacker.name.clone(), target: vs_player.clone() });
+                    }
+                }
+            }
+            //second pass: apply all effects
+            let mut player_factors: Vec<f64> = vec![1.0; num_players];
+            for (target, factor) in adjustments
+            {
+                player_factors[target] *= factor;
+            }
+            let mut floored_players = Vec::new();
+            for i in 0 .. num_players
+            {
+                let correct = answered_correctly(question, &(*access)[i]);
+                count_answer(config, &mut (*access)[i], correct);
+                let money = apply_safe_money(config, (*access)[i].money, versus_rounding(config, (*access)[i].money as f64 * player_factors[i]));
+                //if player has no money, give 1€ to allow continuing the game
+                (*access)[i].money = apply_money_limits(config, money);
+                if money < config.money_floor
+                {
+                    floored_players.push((*access)[i].name.clone());
+                }
+            }
+            apply_lives(config, &previous_player_data, &mut access);
+            data.roster_version.fetch_add(1, Ordering::Relaxed);
+            let player_data = (*access).clone();
+            //create events
+            let mut events = data.write_events().await;
+            push_results_events(config, &mut events, question_id, &(*questions)[question_id - 1], previous_player_data, player_data);
+            if !lives_mode(config)
+            {
+                push_player_on_floor_events(&mut events, floored_players);
+            }
+            for event_data in missing_targets
+            {
+                push_event(&mut events, "VersusTargetMissing", EventType::VersusTargetMissing(event_data));
+            }
+            //set new question state
+            *question_state = QuestionState::Results(false);
+        },
+        QuestionState::MultiSelectQAnswering(true) => { //transition to results state
+            //gather necessary data
+            let question_id = data.current_question.load(Ordering::Relaxed);
+            let questions = data.questions.read().await;
+            let correct_answers = &(*questions)[question_id - 1].correct_answers;
+            //compute the new money of each player
+            let multi_select_q_money = config.multi_select_q_money;
+            let mut access = data.player_data.write().await;
+            let previous_player_data = (*access).clone();
+            let money_range = money_range(&previous_player_data);
+            for player in (*access).iter_mut()
+            {
+                count_answer(config, player, answered_correctly(&(*questions)[question_id - 1], player));
+                let reward = multi_select_reward(&player.answers_selected, correct_answers, multi_select_q_money);
+                let reward = category_reward(config, reward, &(*questions)[question_id - 1].category);
+                player.money = apply_money_limits(config, player.money.saturating_add(comeback_reward(config, reward, player.money, money_range)));
+            }
+            apply_lives(config, &previous_player_data, &mut access);
+            data.roster_version.fetch_add(1, Ordering::Relaxed);
+            let player_data = (*access).clone();
+            //create event
+            let mut events = data.write_events().await;
+            push_results_events(config, &mut events, question_id, &(*questions)[question_id - 1], previous_player_data, player_data);
+            //set new question state
+            *question_state = QuestionState::Results(false);
+        },
+        _ => {},
+    }
+    if *question_state != previous_question_state
+    { //remember to save the game state
+        data.state_dirty.store(true, Ordering::Relaxed);
+        *data.state_began_at.lock().await = Instant::now();
+        //remember the transition, only the last TRANSITION_HISTORY ones are kept
+        let transition_history = config.transition_history;
+        let mut transitions = data.transitions.lock().await;
+        (*transitions).push((*question_state, unix_time_ms()));
+        if (*transitions).len() > transition_history
+        {
+            let excess = (*transitions).len() - transition_history;
+            (*transitions).drain(.. excess);
+        }
+        drop(transitions);
+        if *question_state == QuestionState::Results(false)
+        { //a question was scored
+            kick_idle_players(&data).await;
+        }
+    }
+}
+
+//seconds until the current state of the question is forced to end: the question's bet_time_secs or answer_time_secs if given,
+//else GLOBAL_QUESTION_TIMEOUT_SECS (0 = never)
+async fn question_timeout_secs(data: &GameshowData, question_state: QuestionState) -> u64
+{
+    let questions = data.questions.read().await;
+    let question = data.current_question.load(Ordering::Relaxed).checked_sub(1).and_then(|i| (*questions).get(i));
+    let question_time_secs = match question_state
+    {
+        QuestionState::BettingQBetting(_) => question.and_then(|question| question.bet_time_secs),
+        _ if is_action_allowed(question_state, "answerQuestion") => question.and_then(|question| question.answer_time_secs),
+        _ => None,
+    };
+    question_time_secs.unwrap_or(data.config.global_question_timeout_secs)
+}
+
+//use up the time budget of players who did not answer until it ran out in the current answering state
+async fn exhaust_time_budgets(data: &GameshowData)
+{
+    let elapsed = data.state_began_at.lock().await.elapsed().as_millis() as u64;
+    let mut access = data.player_data.write().await;
+    for player in (*access).iter_mut().filter(|player| !has_answered(player))
+    {
+        if player.time_budget_ms.is_some_and(|budget| budget > 0 && elapsed >= budget)
+        {
+            player.time_budget_ms = Some(0);
+            data.roster_version.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+//count the questions players did not answer in a row and kick them after MAX_MISSED_ROUNDS
+async fn kick_idle_players(data: &GameshowData)
+{
+    let max_missed_rounds = data.config.max_missed_rounds;
+    let mut access = data.player_data.write().await;
+    let mut changed = false;
+    for player in (*access).iter_mut().filter(|player| participates_this_round(&data.config, player))
+    {
+        let missed_rounds = if has_answered(player) { 0 } else { player.missed_rounds + 1 };
+        changed |= missed_rounds != player.missed_rounds;
+        player.missed_rounds = missed_rounds;
+    }
+    //the roster version only changes with the player data (e.g. not when everyone answered again)
+    if changed
+    {
+        data.roster_version.fetch_add(1, Ordering::Relaxed);
+    }
+    if max_missed_rounds == 0
+    {
+        return;
+    }
+    let idle_players: Vec<String> = (*access).iter().filter(|player| player.missed_rounds >= max_missed_rounds)
+        .map(|player| player.name.clone()).collect();
+    if idle_players.is_empty()
+    {
+        return;
+    }
+    (*access).retain(|player| player.missed_rounds < max_missed_rounds);
+    *data.player_index.write().await = build_player_index(&access);
+    data.roster_version.fetch_add(1, Ordering::Relaxed);
+    let mut pins = data.player_pins.write().await;
+    let mut events = data.write_events().await;
+    for name in idle_players
+    {
+        (*pins).remove(&name);
+        push_event(&mut events, "PlayerLeft", EventType::PlayerLeft(EventPlayerLeft { name }));
+    }
+}
+
+//finally remove the soft kicked players, whose grace is over
+async fn remove_kicked_players(data: &GameshowData)
+{
+    let now = Instant::now();
+    let is_due = |player: &PlayerData| player.pending_removal.is_some_and(|until| until <= now);
+    if !data.player_data.read().await.iter().any(is_due)
+    {
+        return;
+    }
+    let mut access = data.player_data.write().await;
+    let kicked_players: Vec<String> = (*access).iter().filter(|player| is_due(player)).map(|player| player.name.clone()).collect();
+    (*access).retain(|player| !is_due(player));
+    *data.player_index.write().await = build_player_index(&access);
+    data.roster_version.fetch_add(1, Ordering::Relaxed);
+    let mut pins = data.player_pins.write().await;
+    let mut events = data.write_events().await;
+    for name in kicked_players
+    {
+        (*pins).remove(&name);
+        push_event(&mut events, "PlayerLeft", EventType::PlayerLeft(EventPlayerLeft { name }));
+    }
+}
+
+//snapshot of the game state that is saved to STATE_FILE
+#[derive(Serialize, Deserialize)]
+struct SavedState
+{
+    question_state: QuestionState,
+    current_question: usize,
+    player_data: Vec<PlayerData>,
+    player_pins: HashMap<String, String>,
+    game_events: Vec<Event>,
+}
+
+//save the game state to STATE_FILE (if it is configured)
+async fn save_state(data: &GameshowData) -> std::io::Result<()>
+{
+    let state_file = match &data.config.state_file
+    {
+        Some(state_file) => state_file,
+        None => { return Ok(()); },
+    };
+    let question_state = data.current_question_state.read().await;
+    let player_data = data.player_data.read().await;
+    let player_pins = data.player_pins.read().await;
+    let game_events = data.game_events.read().await;
+    let saved_state = SavedState { question_state: *question_state, current_question: data.current_question.load(Ordering::Relaxed),
+        player_data: (*player_data).clone(), player_pins: (*player_pins).clone(), game_events: (*game_events).clone() };
+    fs::write(state_file, serde_json::to_string(&saved_state)?)
+}
+
+//restore the game state from STATE_FILE at startup (if it is configured and exists), returns whether it was restored
+//(the questions are not part of the state, so they must be the same as when saving)
+async fn restore_state(data: &GameshowData) -> std::io::Result<bool>
+{
+    let state_file = match &data.config.state_file
+    {
+        Some(state_file) if Path::new(state_file).exists() => state_file,
+        _ => { return Ok(false); },
+    };
+    let saved_state: SavedState = serde_json::from_str(&fs::read_to_string(state_file)?)?;
+    if saved_state.current_question > data.questions.read().await.len()
+    {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "the current question is not in the loaded questions"));
+    }
+    *data.current_question_state.write().await = saved_state.question_state;
+    data.current_question.store(saved_state.current_question, Ordering::Relaxed);
+    *data.player_index.write().await = build_player_index(&saved_state.player_data);
+    *data.player_data.write().await = saved_state.player_data;
+    *data.player_pins.write().await = saved_state.player_pins;
+    *data.game_events.write().await = saved_state.game_events;
+    data.roster_version.fetch_add(1, Ordering::Relaxed);
+    Ok(true)
+}
+
+
+//index site of API as information for me, which API interfaces are available. should not be visible not for users!
+#[get("/api/")]
+async fn index() -> impl Responder
+{
+    NamedFile::open("API-Overview.htm")
+}
+
+//join / register new player; struct for accepting the GET parameters
+#[derive(Serialize, Deserialize)]
+struct JoinPlayerData
+{
+    name: String,
+    pin: Option<String>, //optional PIN chosen at the first join, needed to reclaim the player afterwards
+}
+#[derive(Serialize, Deserialize)]
+struct JoinPlayerResult
+{
+    name: String,
+    reconnected: bool, //true if the player existed already
+    money: i64,
+    jokers: usize,
+}
+#[get("/api/joinPlayer")]
+async fn join_player(data: web::Data<GameshowData>, params: web::Query<JoinPlayerData>) -> impl Responder
+{
+    let trimmed_name = String::from(params.name.trim());
+    if trimmed_name == ""
+    {
+        return HttpResponse::BadRequest().body("Empty name is not allowed!");
+    }
+
+    let mut access = data.player_data.write().await;
+    let mut player_index = data.player_index.write().await;
+    let mut pins = data.player_pins.write().await;
+    if let Some(&i) = (*player_index).get(&trimmed_name)
+    {
+        let player = &mut (*access)[i]; //player is contained already, so it is a reconnect (only with the right PIN if the player chose one)
+        if let Some(pin) = (*pins).get(&trimmed_name)
+        {
+            if params.pin.as_ref() != Some(pin)
+            {
+                return HttpResponse::Forbidden().body("Wrong PIN for this player!");
+            }
+        }
+        //reconnecting within the grace cancels a soft kick
+        if player.pending_removal.take().is_some()
+        {
+            data.roster_version.fetch_add(1, Ordering::Relaxed);
+        }
+        return HttpResponse::Ok().json(JoinPlayerResult { name: trimmed_name, reconnected: true, money: player.money, jokers: player.jokers });
+    }
+
+    let initial_money = data.config.initial_money;
+    let initial_jokers = data.config.initial_jokers;
+    let initial_lives = data.config.initial_lives;
+    let time_budget_ms = data.config.time_budget_ms;
+    let new_player = PlayerData { name: trimmed_name.clone(), jokers: initial_jokers, money: initial_money,
+        money_bet: 0, vs_player: "".to_owned(), vs_players: Vec::new(), answer: 0, answers_selected: Vec::new(), estimate_f: None,
+        missed_rounds: 0, disabled: false, lives: initial_lives, eliminated: false, elimination_order: None, correct_count: 0, answered_count: 0, streak: 0,
+        time_budget_ms: if time_budget_ms > 0 { Some(time_budget_ms) } else { None }, answered_at: None, removed_answers: Vec::new(), bonus_spun: false,
+        pending_removal: None };
+    (*player_index).insert(trimmed_name.clone(), (*access).len());
+    (*access).push(new_player);
+    if let Some(pin) = &params.pin
+    {
+        if !pin.is_empty()
+        {
+            (*pins).insert(trimmed_name.clone(), pin.clone());
+        }
+    }
+    data.roster_version.fetch_add(1, Ordering::Relaxed);
+    let mut events = data.write_events().await;
+    push_event(&mut events, "PlayerJoined", EventType::PlayerJoined(EventPlayerJoined { name: trimmed_name.clone() }));
+
+    HttpResponse::Ok().json(JoinPlayerResult { name: trimmed_name, reconnected: false, money: initial_money, jokers: initial_jokers })
+}
+
+//list all registered players' data (also given answers leaked!)
+#[get("/api/getPlayerData")]
+async fn get_player_data(data: web::Data<GameshowData>) -> impl Responder
+{
+    let question_state = data.current_question_state.read().await;
+    let access = data.player_data.read().await;
+    let mut player_data = public_player_data(&data.config, &access, *question_state);
+    let events = data.game_events.read().await;
+    hide_unrevealed_answers(&data.config, &mut player_data, *question_state, &events);
+    let roster_version = data.roster_version.load(Ordering::Relaxed);
+
+    HttpResponse::Ok().header("X-Roster-Version", roster_version.to_string()).json(player_data)
+}
+
+//list all registered players' data including the live versus selections (for the host)
+#[get("/api/getPlayerDataFull")]
+async fn get_player_data_full(data: web::Data<GameshowData>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    let question_state = data.current_question_state.read().await;
+    let access = data.player_data.read().await;
+    let mut player_data = (*access).clone();
+    let events = data.game_events.read().await;
+    hide_unrevealed_answers(&data.config, &mut player_data, *question_state, &events);
+    let roster_version = data.roster_version.load(Ordering::Relaxed);
+
+    HttpResponse::Ok().header("X-Roster-Version", roster_version.to_string()).json(player_data)
+}
+
+//list all registered players' data together with the roster version, returns 304 if the given version is still current
+#[derive(Serialize, Deserialize)]
+struct PlayerDataVersionParams
+{
+    version: Option<u64>,
+}
+#[derive(Serialize, Deserialize)]
+struct PlayerDataVersioned
+{
+    version: u64,
+    players: Vec<PlayerData>,
+}
+#[get("/api/playerData")]
+async fn get_player_data_versioned(data: web::Data<GameshowData>, params: web::Query<PlayerDataVersionParams>) -> impl Responder
+{
+    let question_state = data.current_question_state.read().await;
+    let access = data.player_data.read().await;
+    let roster_version = data.roster_version.load(Ordering::Relaxed);
+    if params.version == Some(roster_version)
+    {
+        return HttpResponse::NotModified().finish();
+    }
+
+    let mut player_data = public_player_data(&data.config, &access, *question_state);
+    let events = data.game_events.read().await;
+    hide_unrevealed_answers(&data.config, &mut player_data, *question_state, &events);
+    HttpResponse::Ok().json(PlayerDataVersioned { version: roster_version, players: player_data })
+}
+
+//accept the bets, but only when in betting question state
+#[derive(Serialize, Deserialize)]
+struct BetMoneyData
+{
+    name: String,
+    money_bet: i64,
+}
+#[get("/api/betMoney")]
+async fn bet_money_get(data: web::Data<GameshowData>, params: web::Query<BetMoneyData>) -> impl Responder
+{
+    bet_money(data, &params).await
+}
+#[post("/api/betMoney")]
+async fn bet_money_post(data: web::Data<GameshowData>, params: web::Json<BetMoneyData>) -> impl Responder
+{
+    bet_money(data, &params).await
+}
+async fn bet_money(data: web::Data<GameshowData>, params: &BetMoneyData) -> HttpResponse
+{
+    //ensure current question state is betting, else return not acceptable
+    {
+        let question_state = data.current_question_state.read().await;
+        if !is_action_allowed(*question_state, "betMoney")
+        {
+            return HttpResponse::NotAcceptable().body("QuestionState is not Betting(false)!");
+        }
+    }
+    
+    //ensure the bet is one of the allowed bets, if they are configured by ALLOWED_BETS (comma-separated)
+    let allowed_bets = &data.config.allowed_bets;
+    if !allowed_bets.is_empty() && !allowed_bets.contains(&params.money_bet)
+    {
+        let allowed_bets: Vec<String> = allowed_bets.iter().map(|bet| bet.to_string()).collect();
+        return HttpResponse::BadRequest().body(format!("money_bet is not one of the allowed bets {}!", allowed_bets.join(", ")));
+    }
+    
+    //ensure the bet does not exceed the question's maximum bet
+    let min_bet;
+    {
+        let question_id = data.current_question.load(Ordering::Relaxed);
+        let questions = data.questions.read().await;
+        if let Some(max_bet) = (*questions)[question_id - 1].max_bet
+        {
+            if params.money_bet > max_bet
+            {
+                return HttpResponse::BadRequest().body(format!("money_bet is above the maximum bet of {}!", max_bet));
+            }
+        }
+        min_bet = (*questions)[question_id - 1].min_bet;
+    }
+    
+    //perform money betting
+    {
+        let mut access = data.player_data.write().await;
+        let player_index = data.player_index.read().await;
+        let player = match (*player_index).get(&params.name)
+        {
+            Some(&i) => &mut (*access)[i],
+            None => { return HttpResponse::BadRequest().body("Player name was not found!"); },
+        };
+        if player.disabled
+        {
+            return HttpResponse::build(StatusCode::LOCKED).body("Player is disabled!");
+        }
+        if !participates_this_round(&data.config, player)
+        {
+            return HttpResponse::build(StatusCode::LOCKED).body("Player does not take part in this question!");
+        }
+        if params.money_bet < 1 || player.money < params.money_bet
+        {
+            return HttpResponse::BadRequest().body("money_bet is invalid (< 1 or > player money)!");
+        }
+        if let Some(min_bet) = min_bet
+        { //players with less money than the minimum bet can still go all-in
+            if params.money_bet < min_bet && params.money_bet != player.money
+            {
+                return HttpResponse::BadRequest().body(format!("money_bet is below the minimum bet of {}!", min_bet));
+            }
+        }
+        //set player's money_bet
+        player.money_bet = params.money_bet;
+        data.roster_version.fetch_add(1, Ordering::Relaxed);
+    }
+    
+    //check if all players have bet to indicate abilitiy to proceed
+    let mut question_state = data.current_question_state.write().await;
+    let round_ready = is_round_ready(&data.config, &data.player_data.read().await, *question_state);
+    if round_ready
+    {
+        set_round_ready(&data, &mut question_state).await;
+    }
+    
+    HttpResponse::Ok().finish()
+}
+
+//read a list of names given comma-separated (query) or as sequence (JSON)
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrList
+{
+    String(String),
+    List(Vec<String>),
+}
+fn string_or_list<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+    where D: serde::Deserializer<'de>
+{
+    Ok(match Option::<StringOrList>::deserialize(deserializer)?
+    {
+        Some(StringOrList::String(names)) => names.split(',').map(|name| name.trim().to_owned()).filter(|name| !name.is_empty()).collect(),
+        Some(StringOrList::List(names)) => names,
+        None => Vec::new(),
+    })
+}
+
+//read a list of answer IDs given comma-separated (query) or as sequence of numbers (JSON), as comma-separated text
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumbers
+{
+    String(String),
+    Numbers(Vec<usize>),
+}
+fn string_or_numbers<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+    where D: serde::Deserializer<'de>
+{
+    Ok(match Option::<StringOrNumbers>::deserialize(deserializer)?
+    {
+        Some(StringOrNumbers::String(answers)) => Some(answers),
+        Some(StringOrNumbers::Numbers(answers)) => Some(answers.iter().map(|answer| answer.to_string()).collect::<Vec<String>>().join(",")),
+        None => None,
+    })
+}
+
+//accept the versus selection, but only when in selecting question state
+#[derive(Serialize, Deserialize)]
+struct AttackPlayerData
+{
+    name: String,
+    #[serde(default)]
+    vs_player: String,
+    #[serde(default, deserialize_with = "string_or_list")]
+    vs_players: Vec<String>, //multiple attacked players (up to VERSUS_MAX_TARGETS), comma-separated or as JSON array
+}
+#[get("/api/attackPlayer")]
+async fn attack_player_get(data: web::Data<GameshowData>, params: web::Query<AttackPlayerData>) -> impl Responder
+{
+    attack_player(data, &params).await
+}
+#[post("/api/attackPlayer")]
+async fn attack_player_post(data: web::Data<GameshowData>, params: web::Json<AttackPlayerData>) -> impl Responder
+{
+    attack_player(data, &params).await
+}
+async fn attack_player(data: web::Data<GameshowData>, params: &AttackPlayerData) -> HttpResponse
+{
+    //ensure current question state is selecting, else return not acceptable
+    {
+        let question_state = data.current_question_state.read().await;
+        if !is_action_allowed(*question_state, "attackPlayer")
+        {
+            return HttpResponse::NotAcceptable().body("QuestionState is not VersusQSelecting(false)!");
+        }
+    }
+    
+    //parse the attacked players
+    let mut vs_players: Vec<String> = Vec::new();
+    for vs_player in params.vs_players.iter()
+    {
+        if !vs_players.contains(vs_player)
+        {
+            vs_players.push(vs_player.clone());
+        }
+    }
+    if vs_players.is_empty()
+    {
+        vs_players.push(params.vs_player.clone());
+    }
+    let versus_max_targets = data.config.versus_max_targets;
+    if vs_players.len() > versus_max_targets
+    {
+        return HttpResponse::BadRequest().body(format!("Too many vs_players (at most {})!", versus_max_targets));
+    }
+    
+    //perform selecting
+    {
+        if vs_players.contains(&params.name)
+        {
+            return HttpResponse::BadRequest().body("name and vs_player are equal!");
+        }
+        
+        let mut access = data.player_data.write().await;
+        let player_index = data.player_index.read().await;
+        let i = match (*player_index).get(&params.name)
+        {
+            Some(&i) => i,
+            None => { return HttpResponse::BadRequest().body("Player name was not found!"); },
+        };
+        if (*access)[i].disabled
+        {
+            return HttpResponse::build(StatusCode::LOCKED).body("Player is disabled!");
+        }
+        if !participates_this_round(&data.config, &(*access)[i])
+        {
+            return HttpResponse::build(StatusCode::LOCKED).body("Player does not take part in this question!");
+        }
+        if !vs_players.iter().all(|vs_player| (*player_index).contains_key(vs_player))
+        {
+            return HttpResponse::BadRequest().body("Player vs_player was not found!");
+        }
+        
+        //set player's selection
+        (*access)[i].vs_player = vs_players[0].clone();
+        (*access)[i].vs_players = vs_players;
+        data.roster_version.fetch_add(1, Ordering::Relaxed);
+    }
+    
+    //check if all players have selected to indicate abilitiy to proceed
+    let mut question_state = data.current_question_state.write().await;
+    let round_ready = is_round_ready(&data.config, &data.player_data.read().await, *question_state);
+    if round_ready
+    {
+        set_round_ready(&data, &mut question_state).await;
+    }
+    
+    HttpResponse::Ok().finish()
+}
+
+//accept the question answer
+#[derive(Serialize, Deserialize)]
+struct AnswerQuestionData
+{
+    name: String,
+    #[serde(default)]
+    answer: usize,
+    #[serde(default, deserialize_with = "string_or_numbers")]
+    answers: Option<String>, //selected answers for multi-select questions, comma-separated or as JSON array
+    estimate_f: Option<f64>, //decimal answer for estimation questions
+}
+#[get("/api/answerQuestion")]
+async fn answer_question_get(data: web::Data<GameshowData>, params: web::Query<AnswerQuestionData>) -> impl Responder
+{
+    answer_question(data, &params).await
+}
+#[post("/api/answerQuestion")]
+async fn answer_question_post(data: web::Data<GameshowData>, params: web::Json<AnswerQuestionData>) -> impl Responder
+{
+    answer_question(data, &params).await
+}
+async fn answer_question(data: web::Data<GameshowData>, params: &AnswerQuestionData) -> HttpResponse
+{
+    //ensure current question state is answering, else return not acceptable
+    {
+        let question_state = data.current_question_state.read().await;
+        if !is_action_allowed(*question_state, "answerQuestion")
+        {
+            return HttpResponse::NotAcceptable().body("QuestionState is not *Answering(false)!");
+        }
+    }
+    
+    //parse selected answers for multi-select questions
+    let mut answers_selected = Vec::new();
+    {
+        let question_id = data.current_question.load(Ordering::Relaxed);
+        let questions = data.questions.read().await;
+        let question = &(*questions)[question_id - 1];
+        if question.question_type == QuestionType::MultiSelectQuestion
+        {
+            let answers = match &params.answers
+            {
+                Some(answers) => answers.trim().trim_start_matches('[').trim_end_matches(']'),
+                None => { return HttpResponse::BadRequest().body("answers are required for multi-select questions!"); },
+            };
+            for answer in answers.split(',')
+            {
+                match answer.trim().parse::<usize>()
+                {
+                    Ok(answer) if answer >= 1 && answer <= question.answers.len() => {
+                        if !answers_selected.contains(&answer)
+                        {
+                            answers_selected.push(answer);
+                        }
+                    },
+                    _ => { return HttpResponse::BadRequest().body("answers are invalid (must be 1 - len(answers))!"); },
+                }
+            }
+        }
+        else if params.estimate_f.is_some()
+        {
+            if question.question_type != QuestionType::EstimationQuestion || !params.estimate_f.unwrap().is_finite()
+            {
+                return HttpResponse::BadRequest().body("estimate_f is only allowed as finite number for estimation questions!");
+            }
+        }
+        else if params.answer < 1
+        {
+            return HttpResponse::BadRequest().body("answer is invalid (< 1)!");
+        }
+    }
+    
+    //perform answering
+    {
+        let state_began_at = *data.state_began_at.lock().await;
+        let mut access = data.player_data.write().await;
+        let player_index = data.player_index.read().await;
+        let player = match (*player_index).get(&params.name)
+        {
+            Some(&i) => &mut (*access)[i],
+            None => { return HttpResponse::BadRequest().body("Player name was not found!"); },
+        };
+        if player.disabled
+        {
+            return HttpResponse::build(StatusCode::LOCKED).body("Player is disabled!");
+        }
+        if !participates_this_round(&data.config, player)
+        {
+            return HttpResponse::build(StatusCode::LOCKED).body("Player does not take part in this question!");
+        }
+        //set player's answer
+        let answer_lock = data.config.answer_lock;
+        if answer_lock && has_answered(player)
+        {
+            return HttpResponse::Conflict().body("Player already answered and answers are locked!");
+        }
+        //use up the time budget since the question's begin (or since the player's previous answer to it)
+        let now = Instant::now();
+        if let Some(budget) = player.time_budget_ms
+        {
+            let used = now.duration_since(player.answered_at.unwrap_or(state_began_at)).as_millis() as u64;
+            if used >= budget
+            {
+                player.time_budget_ms = Some(0);
+                data.roster_version.fetch_add(1, Ordering::Relaxed);
+                return HttpResponse::Forbidden().body("Player's time budget is exhausted!");
+            }
+            player.time_budget_ms = Some(budget - used);
+        }
+        player.answer = params.answer;
+        player.answers_selected = answers_selected;
+        player.estimate_f = params.estimate_f;
+        player.answered_at = Some(now);
+        data.roster_version.fetch_add(1, Ordering::Relaxed);
+    }
+    
+    //check if all players have answered to indicate abilitiy to proceed
+    let mut question_state = data.current_question_state.write().await;
+    let round_ready = is_round_ready(&data.config, &data.player_data.read().await, *question_state);
+    if round_ready
+    {
+        set_round_ready(&data, &mut question_state).await;
+    }
+    
+    HttpResponse::Ok().finish()
+}
+
+//get 50/50 joker for current question (only for betting questions!)
+#[derive(Serialize, Deserialize)]
+struct GetJokerData
+{
+    name: String,
+}
+#[get("/api/getJokerFiftyFifty")]
+async fn get_joker_fifty_fifty_get(data: web::Data<GameshowData>, params: web::Query<GetJokerData>) -> impl Responder
+{
+    get_joker_fifty_fifty(data, &params).await
+}
+#[post("/api/getJokerFiftyFifty")]
+async fn get_joker_fifty_fifty_post(data: web::Data<GameshowData>, params: web::Json<GetJokerData>) -> impl Responder
+{
+    get_joker_fifty_fifty(data, &params).await
+}
+async fn get_joker_fifty_fifty(data: web::Data<GameshowData>, params: &GetJokerData) -> HttpResponse
+{
+    //ensure current question state is answering for normal or betting question, else return not acceptable
+    {
+        let question_state = data.current_question_state.read().await;
+        if !is_action_allowed(*question_state, "getJokerFiftyFifty")
+        {
+            return HttpResponse::NotAcceptable().body("QuestionState is not BettingQAnswering(false)!");
+        }
+    }
+    
+    //get wrong answers
+    let wrong_answers: Vec<usize>;
+    {
+        let current_question = data.current_question.load(Ordering::Relaxed);
+        let questions = data.questions.read().await;
+        let question = &(*questions)[current_question - 1];
+        //answer IDs are 1-based, a malformed question must not give away the correct answer
+        if question.correct_answer < 1 || question.correct_answer > question.answers.len()
+        {
+            return HttpResponse::InternalServerError().body("correct_answer of the question is invalid (must be 1 - len(answers))!");
+        }
+        let choose_from: Vec<usize> = (1 ..= question.answers.len()).filter(|answer| !is_correct_answer(question, *answer)).collect();
+        let mut rng = data.rng.lock().await;
+        wrong_answers = choose_from.choose_multiple(&mut *rng, 2).copied().collect();
+    }
+    
+    //send wrong answers
+    let mut access = data.player_data.write().await;
+    let player_index = data.player_index.read().await;
+    let player = match (*player_index).get(&params.name)
+    {
+        Some(&i) => &mut (*access)[i],
+        None => { return HttpResponse::BadRequest().body("Player name was not found!"); },
+    };
+    if player.disabled
+    {
+        return HttpResponse::build(StatusCode::LOCKED).body("Player is disabled!");
+    }
+    if player.jokers < 1
+    {
+        return HttpResponse::NotAcceptable().body("No jokers available!");
+    }
+    player.jokers -= 1;
+    for answer in wrong_answers.iter()
+    {
+        if !player.removed_answers.contains(answer)
+        {
+            player.removed_answers.push(*answer);
+        }
+    }
+    data.jokers_used.fetch_add(1, Ordering::Relaxed);
+    data.roster_version.fetch_add(1, Ordering::Relaxed);
+    HttpResponse::Ok().json(wrong_answers)
+}
+
+//get audience poll joker for current question (only for normal and betting questions!), the percentages of the answers
+#[get("/api/getJokerAudiencePoll")]
+async fn get_joker_audience_poll_get(data: web::Data<GameshowData>, params: web::Query<GetJokerData>) -> impl Responder
+{
+    get_joker_audience_poll(data, &params).await
+}
+#[post("/api/getJokerAudiencePoll")]
+async fn get_joker_audience_poll_post(data: web::Data<GameshowData>, params: web::Json<GetJokerData>) -> impl Responder
+{
+    get_joker_audience_poll(data, &params).await
+}
+async fn get_joker_audience_poll(data: web::Data<GameshowData>, params: &GetJokerData) -> HttpResponse
+{
+    //ensure current question state is answering for normal or betting question, else return not acceptable
+    {
+        let question_state = data.current_question_state.read().await;
+        if !is_action_allowed(*question_state, "getJokerAudiencePoll")
+        {
+            return HttpResponse::NotAcceptable().body("QuestionState is not NormalQAnswering(false) or BettingQAnswering(false)!");
+        }
+    }
+    
+    let num_answers;
+    {
+        let current_question = data.current_question.load(Ordering::Relaxed);
+        let questions = data.questions.read().await;
+        num_answers = (*questions)[current_question - 1].answers.len();
+    }
+    
+    let mut access = data.player_data.write().await;
+    let player_index = data.player_index.read().await;
+    let i = match (*player_index).get(&params.name)
+    {
+        Some(&i) => i,
+        None => { return HttpResponse::BadRequest().body("Player name was not found!"); },
+    };
+    if (*access)[i].disabled
+    {
+        return HttpResponse::build(StatusCode::LOCKED).body("Player is disabled!");
+    }
+    if (*access)[i].jokers < 1
+    {
+        return HttpResponse::NotAcceptable().body("No jokers available!");
+    }
+    
+    //get the poll's weights of the answers
+    let audience_poll_from_answers = data.config.audience_poll_from_answers;
+    let weights: Vec<f64> = if audience_poll_from_answers
+    { //count the other players' answers, every answer gets one vote more so that no answer is exactly zero
+        let mut votes = vec![1.0; num_answers];
+        let mut num_votes = 0;
+        for (j, player) in (*access).iter().enumerate()
+        {
+            if j != i && player.answer >= 1 && player.answer <= num_answers
+            {
+                votes[player.answer - 1] += 1.0;
+                num_votes += 1;
+            }
+        }
+        let audience_poll_min_answers = data.config.audience_poll_min_answers;
+        if num_votes < audience_poll_min_answers
+        {
+            return HttpResponse::Conflict().body("Not enough answers for the audience poll yet!");
+        }
+        votes
+    }
+    else
+    {
+        let mut rng = data.rng.lock().await;
+        (0 .. num_answers).map(|_| rng.gen::<f64>()).collect()
+    };
+    
+    //send the poll
+    (*access)[i].jokers -= 1;
+    data.jokers_used.fetch_add(1, Ordering::Relaxed);
+    data.roster_version.fetch_add(1, Ordering::Relaxed);
+    HttpResponse::Ok().json(poll_percentages(&weights))
+}
+
+//convert the weights to whole percentages summing up to 100 (the biggest remainders are rounded up)
+fn poll_percentages(weights: &[f64]) -> Vec<usize>
+{
+    let total: f64 = weights.iter().sum();
+    let exact: Vec<f64> = weights.iter().map(|weight| weight * 100.0 / total).collect();
+    let mut percentages: Vec<usize> = exact.iter().map(|percentage| percentage.floor() as usize).collect();
+    let mut by_remainder: Vec<usize> = (0 .. exact.len()).collect();
+    by_remainder.sort_by(|&a, &b| (exact[b] - exact[b].floor()).total_cmp(&(exact[a] - exact[a].floor())));
+    let missing = 100usize.saturating_sub(percentages.iter().sum());
+    for &i in by_remainder.iter().take(missing)
+    {
+        percentages[i] += 1;
+    }
+    percentages
+}
+
+//get the answer IDs of the current question, which were not removed by the player's jokers (only the player itself with the PIN)
+#[derive(Serialize, Deserialize)]
+struct MyOptionsData
+{
+    name: String,
+    pin: Option<String>,
+}
+#[get("/api/myOptions")]
+async fn get_my_options(data: web::Data<GameshowData>, params: web::Query<MyOptionsData>) -> impl Responder
+{
+    let current_question = data.current_question.load(Ordering::Relaxed);
+    let questions = data.questions.read().await;
+    if current_question < 1 || current_question > (*questions).len()
+    {
+        return HttpResponse::NotAcceptable().body("There is no current question!");
+    }
+    let access = data.player_data.read().await;
+    let player_index = data.player_index.read().await;
+    let player = match (*player_index).get(&params.name)
+    {
+        Some(&i) => &(*access)[i],
+        None => { return HttpResponse::BadRequest().body("Player name was not found!"); },
+    };
+    let pins = data.player_pins.read().await;
+    if (*pins).get(&params.name).is_some_and(|pin| params.pin.as_ref() != Some(pin))
+    {
+        return HttpResponse::Forbidden().body("Wrong PIN for this player!");
+    }
+    let options: Vec<usize> = (1 ..= (*questions)[current_question - 1].answers.len())
+        .filter(|answer| !player.removed_answers.contains(answer)).collect();
+    HttpResponse::Ok().json(options)
+}
+
+//check if the event concerns the player (the results of every question are not counted, only the round's winners)
+fn event_mentions_player(event: &EventType, name: &str) -> bool
+{
+    match event
+    {
+        EventType::BetsLocked(event) => event.bets.as_ref().is_some_and(|bets| bets.iter().any(|(player, _)| player == name)),
+        EventType::BeginBettingQAnswering(event) => event.bets.as_ref().is_some_and(|bets| bets.iter().any(|(player, _)| player == name)),
+        EventType::RoundSummary(event) => event.winners.iter().any(|winner| winner == name) ||
+            event.biggest_gain.as_ref().is_some_and(|(player, _)| player == name),
+        EventType::RevealPlayer(event) => event.name == name,
+        EventType::PlayerOnFloor(event) => event.name == name,
+        EventType::VersusTargetMissing(event) => event.attacker == name || event.target == name,
+        EventType::DefaultBetApplied(event) => event.players.iter().any(|player| player == name),
+        EventType::BetClamped(event) => event.name == name,
+        EventType::PlayerJoined(event) => event.name == name,
+        EventType::PlayerLeft(event) => event.name == name,
+        EventType::BonusSpin(event) => event.name == name,
+        EventType::TiebreakerResolved(event) => event.winner == name,
+        EventType::GameEnding(event) => event.player_data.iter().any(|player| player.name == name),
+        _ => false,
+    }
+}
+
+//get the events concerning one player (e.g. for a personal recap)
+#[derive(Serialize, Deserialize)]
+struct PlayerEventsData
+{
+    name: String,
+}
+#[get("/api/playerEvents")]
+async fn get_player_events(data: web::Data<GameshowData>, params: web::Query<PlayerEventsData>) -> impl Responder
+{
+    let access = data.game_events.read().await;
+    let data: Vec<Event> = (*access).iter()
+        .filter(|event| event_mentions_player(&event.event, &params.name))
+        .cloned().collect();
+    
+    HttpResponse::Ok().json(data)
+}
+
+//get current status and game commands, optionally only events after the event ID since and/or of one type
+#[derive(Serialize, Deserialize)]
+struct GetGameEventsData
+{
+    since: Option<usize>,
+    #[serde(rename = "type")]
+    event_type: Option<String>,
+}
+#[get("/api/getGameEvents")]
+async fn get_game_events(data: web::Data<GameshowData>, params: web::Query<GetGameEventsData>) -> impl Responder
+{
+    let access = data.game_events.read().await;
+    let data: Vec<Event> = (*access).iter()
+        .filter(|event| params.since.is_none_or(|since| event.id > since))
+        .filter(|event| params.event_type.as_ref().is_none_or(|event_type| &event.event_name == event_type))
+        .cloned().collect();
+    
+    HttpResponse::Ok().json(data)
+}
+
+//get a single event by its ID (the events are sorted by ID, so it is a binary search)
+#[get("/api/event/{id}")]
+async fn get_event(data: web::Data<GameshowData>, path: web::Path<usize>) -> impl Responder
+{
+    let id = path.into_inner();
+    let access = data.game_events.read().await;
+    match (*access).binary_search_by_key(&id, |event| event.id)
+    {
+        Ok(position) => HttpResponse::Ok().json(&(*access)[position]),
+        Err(_) => HttpResponse::NotFound().body("Event was not found!"),
+    }
+}
+
+//long-poll: wait until there are events after the event ID since, then return them together with the player data
+#[derive(Serialize, Deserialize)]
+struct PollData
+{
+    since: Option<usize>,
+}
+#[derive(Serialize, Deserialize)]
+struct PollResult
+{
+    events: Vec<Event>,
+    players: Vec<PlayerData>,
+}
+#[get("/api/poll")]
+async fn poll(data: web::Data<GameshowData>, params: web::Query<PollData>) -> impl Responder
+{
+    let poll_timeout_secs = data.config.poll_timeout_secs;
+    let deadline = Instant::now() + Duration::from_secs(poll_timeout_secs);
+    loop
+    {
+        //registered before checking, so that events written in between are not missed
+        let written = data.events_written.notified();
+        {
+            let question_state = data.current_question_state.read().await;
+            let players = data.player_data.read().await;
+            let access = data.game_events.read().await;
+            let events: Vec<Event> = (*access).iter()
+                .filter(|event| params.since.is_none_or(|since| event.id > since))
+                .cloned().collect();
+            if !events.is_empty()
+            {
+                let mut player_data = public_player_data(&data.config, &players, *question_state);
+                hide_unrevealed_answers(&data.config, &mut player_data, *question_state, &access);
+                return HttpResponse::Ok().json(PollResult { events, players: player_data });
+            }
+        }
+        let now = Instant::now();
+        if now >= deadline
+        {
+            return HttpResponse::NoContent().finish();
+        }
+        //wait for the next write of the events (or the deadline) and check again
+        let _ = actix_web::rt::time::timeout(deadline - now, written).await;
+    }
+}
+
+//leaderboard of correctly answered questions (independent of money)
+#[derive(Serialize, Deserialize)]
+struct AccuracyData
+{
+    name: String,
+    correct_count: usize,
+    answered_count: usize,
+}
+#[get("/api/accuracy")]
+async fn get_accuracy(data: web::Data<GameshowData>) -> impl Responder
+{
+    let access = data.player_data.read().await;
+    let mut accuracy: Vec<AccuracyData> = (*access).iter().map(|player| AccuracyData { name: player.name.clone(),
+        correct_count: player.correct_count, answered_count: player.answered_count }).collect();
+    accuracy.sort_by_key(|player| std::cmp::Reverse(player.correct_count));
+    HttpResponse::Ok().json(accuracy)
+}
+
+//stream all events from the start and then the new ones live as server-sent events, strictly ordered by ID
+#[get("/api/replay")]
+async fn replay(data: web::Data<GameshowData>) -> impl Responder
+{
+    //the stream remembers the next event ID to send, so history and live events neither overlap nor leave gaps
+    let stream = futures::stream::unfold((data, 0), |(data, next_id)| async move {
+        loop
+        {
+            {
+                let access = data.game_events.read().await;
+                let events: Vec<&Event> = (*access).iter().filter(|event| event.id >= next_id).collect();
+                if let Some(last) = events.last()
+                {
+                    let next_id = last.id + 1;
+                    let mut frames = String::new();
+                    for event in events
+                    {
+                        frames += &format!("id: {}\nevent: {}\ndata: {}\n\n", event.id, event.event_name,
+                            serde_json::to_string(event).unwrap_or_default());
+                    }
+                    return Some((Ok::<_, actix_web::Error>(web::Bytes::from(frames)), (data.clone(), next_id)));
+                }
+            }
+            actix_web::rt::time::delay_for(Duration::from_millis(100)).await;
+        }
+    });
+    HttpResponse::Ok().content_type("text/event-stream").streaming(Box::pin(stream))
+}
+
+//aggregate statistics of the game (since the questions were loaded), computed from the results events
+#[derive(Serialize, Deserialize)]
+struct SummaryData
+{
+    questions_played: usize, //number of scored questions
+    total_money_awarded: i64, //sum of all money gains in the results
+    jokers_used: usize,
+    biggest_swing: Option<(String, i64)>, //player with the biggest money change in a single question and the change
+    winners: Vec<String>, //players who would win the game now (like in GameEnding)
+}
+#[get("/api/summary")]
+async fn get_summary(data: web::Data<GameshowData>) -> impl Responder
+{
+    let access = data.player_data.read().await;
+    let events = data.game_events.read().await;
+    //only the events of the current game count
+    let game_start = (*events).iter().rposition(|event| event.event_name == "QuestionsLoaded" || event.event_name == "NewGameStarted").map_or(0, |position| position + 1);
+    let mut summary = SummaryData { questions_played: 0, total_money_awarded: 0, jokers_used: data.jokers_used.load(Ordering::Relaxed),
+        biggest_swing: None, winners: Vec::new() };
+    for event in (*events)[game_start ..].iter()
+    {
+        if let EventType::ShowResults(results) = &event.event
+        {
+            summary.questions_played += 1;
+            for (previous, player) in results.previous_player_data.iter().zip(results.player_data.iter())
+            {
+                let change = player.money - previous.money;
+                if change > 0
+                {
+                    summary.total_money_awarded += change;
+                }
+                if summary.biggest_swing.as_ref().is_none_or(|(_, swing)| change.abs() > swing.abs())
+                {
+                    summary.biggest_swing = Some((player.name.clone(), change));
+                }
+            }
+        }
+    }
+    summary.winners = game_ending_event(&data.config, &access).winners;
+    HttpResponse::Ok().json(summary)
+}
+
+//get the current question state
+#[derive(Serialize, Deserialize)]
+struct StateData
+{
+    state: String,
+    ready: bool, //whether the state is ready to transition to the next state (e.g. everyone answered)
+    current_question: usize,
+    num_questions: usize,
+    roster_version: u64,
+    server_time_unix_ms: u64,
+}
+#[get("/api/state")]
+async fn get_state(data: web::Data<GameshowData>) -> impl Responder
+{
+    let question_state = data.current_question_state.read().await;
+    let questions = data.questions.read().await;
+    let (state, ready) = question_state_info(*question_state);
+    HttpResponse::Ok().json(StateData { state: String::from(state), ready, current_question: data.current_question.load(Ordering::Relaxed),
+        num_questions: (*questions).len(), roster_version: data.roster_version.load(Ordering::Relaxed),
+        server_time_unix_ms: unix_time_ms() })
+}
+
+//list the last state transitions with their time (for the host to debug the pacing)
+#[derive(Serialize, Deserialize)]
+struct TransitionData
+{
+    state: String,
+    ready: bool,
+    time_unix_ms: u64,
+}
+#[get("/api/transitions")]
+async fn get_transitions(data: web::Data<GameshowData>) -> impl Responder
+{
+    let transitions = data.transitions.lock().await;
+    let transition_data: Vec<TransitionData> = (*transitions).iter().map(|(question_state, time)| {
+        let (state, ready) = question_state_info(*question_state);
+        TransitionData { state: String::from(state), ready, time_unix_ms: *time }
+    }).collect();
+    HttpResponse::Ok().json(transition_data)
+}
+
+//list the API actions that are allowed in the current question state
+#[derive(Serialize, Deserialize)]
+struct AllowedActionsData
+{
+    state: String,
+    ready: bool,
+    actions: Vec<String>,
+}
+#[get("/api/allowedActions")]
+async fn get_allowed_actions(data: web::Data<GameshowData>) -> impl Responder
+{
+    let question_state = data.current_question_state.read().await;
+    let (state, ready) = question_state_info(*question_state);
+    let actions = allowed_actions(*question_state).iter().map(|action| String::from(*action)).collect();
+    HttpResponse::Ok().json(AllowedActionsData { state: String::from(state), ready, actions })
+}
+
+//get the correct answer of the current question, only after the results to not leak it
+#[derive(Serialize, Deserialize)]
+struct CorrectAnswerData
+{
+    question_number: usize,
+    question: String,
+    correct_answer: usize,
+    answer: Option<String>, //text of the correct option (not existing for estimation questions)
+}
+#[get("/api/correctAnswer")]
+async fn get_correct_answer(data: web::Data<GameshowData>) -> impl Responder
+{
+    //ensure current question state is results or ended game, else return forbidden
+    let question_state = data.current_question_state.read().await;
+    if !is_action_allowed(*question_state, "correctAnswer")
+    {
+        return HttpResponse::Forbidden().body("QuestionState is not Results or GameEnding!");
+    }
+    
+    let questions = data.questions.read().await;
+    //after the game ended, the current question points behind the last question
+    let question_id = data.current_question.load(Ordering::Relaxed).min((*questions).len());
+    if question_id < 1
+    {
+        return HttpResponse::NotFound().body("No question was played yet!");
+    }
+    //the answer is only given away after the question was scored (the last results of the current game belong to it)
+    let events = data.game_events.read().await;
+    let game_start = (*events).iter().rposition(|event| event.event_name == "QuestionsLoaded" || event.event_name == "NewGameStarted").map_or(0, |position| position + 1);
+    let scored_question = (*events)[game_start ..].iter().rev().find_map(|event| match &event.event
+    {
+        EventType::ShowResults(results) => Some(results.question_index),
+        _ => None,
+    });
+    if scored_question != Some(question_id)
+    {
+        return HttpResponse::Forbidden().body("The current question was not scored yet!");
+    }
+    let question = &(*questions)[question_id - 1];
+    let answer = question.answers.get(question.correct_answer.wrapping_sub(1)).cloned();
+    HttpResponse::Ok().json(CorrectAnswerData { question_number: question_id, question: question.question.clone(),
+        correct_answer: question.correct_answer, answer })
+}
+
+//check the host's ADMIN_TOKEN in the X-Admin-Token header, returns the error response if it is missing or wrong
+//(without ADMIN_TOKEN the host-only endpoints are closed)
+fn check_admin_token(config: &Config, req: &HttpRequest) -> Result<(), HttpResponse>
+{
+    let token = req.headers().get("X-Admin-Token").and_then(|token| token.to_str().ok());
+    check_token(config, token, "X-Admin-Token header is missing!")
+}
+
+//compare the given token with ADMIN_TOKEN, missing is the error message if no token was given
+fn check_token(config: &Config, token: Option<&str>, missing: &str) -> Result<(), HttpResponse>
+{
+    match (&config.admin_token, token)
+    {
+        (None, _) => Err(HttpResponse::Forbidden().body("ADMIN_TOKEN is not configured!")),
+        (Some(_), None) => Err(HttpResponse::Unauthorized().body(missing.to_owned())),
+        (Some(admin_token), Some(token)) if token == admin_token => Ok(()),
+        _ => Err(HttpResponse::Forbidden().body("Wrong admin token!")),
+    }
+}
+
+//get a question including the correct answer for the host's preparation (the current question if no number is given)
+#[derive(Serialize, Deserialize)]
+struct AdminQuestionData
+{
+    number: Option<usize>,
+}
+#[get("/api/adminQuestion")]
+async fn get_admin_question(data: web::Data<GameshowData>, params: web::Query<AdminQuestionData>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    let questions = data.questions.read().await;
+    let number = match params.number
+    {
+        Some(number) => number,
+        None => data.current_question.load(Ordering::Relaxed),
+    };
+    if number < 1 || number > (*questions).len()
+    {
+        return HttpResponse::BadRequest().body("Number is not a valid question ID (must be 1 - len(questions))!");
+    }
+    HttpResponse::Ok().json(&(*questions)[number - 1])
+}
+
+//list the types and categories of the questions after the current one (for the host's pacing, without content)
+#[derive(Serialize, Deserialize)]
+struct RemainingQuestionData
+{
+    index: usize,
+    question_type: QuestionType,
+    category: String,
+}
+#[get("/api/remainingQuestions")]
+async fn get_remaining_questions(data: web::Data<GameshowData>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    let questions = data.questions.read().await;
+    let current_question = data.current_question.load(Ordering::Relaxed);
+    let remaining: Vec<RemainingQuestionData> = (*questions).iter().enumerate().skip(current_question)
+        .map(|(i, question)| RemainingQuestionData { index: i + 1, question_type: question.question_type, category: question.category.clone() })
+        .collect();
+    HttpResponse::Ok().json(remaining)
+}
+
+//preview type and category of the question activateNextQuestion would begin, or that the game would end instead
+#[derive(Serialize, Deserialize)]
+struct PeekNextData
+{
+    game_ends: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    question_type: Option<QuestionType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
+}
+#[get("/api/peekNext")]
+async fn peek_next(data: web::Data<GameshowData>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    let questions = data.questions.read().await;
+    let question_id = data.current_question.load(Ordering::Relaxed) + 1;
+    if is_game_over(&data, question_id, (*questions).len()).await
+    {
+        return HttpResponse::Ok().json(PeekNextData { game_ends: true, index: None, question_type: None, category: None });
+    }
+    let question = &(*questions)[question_id - 1];
+    HttpResponse::Ok().json(PeekNextData { game_ends: false, index: Some(question_id), question_type: Some(question.question_type),
+        category: Some(question.category.clone()) })
+}
+
+//download all loaded questions including the correct answers in the questions file format (for the host's backups)
+#[get("/api/currentQuestions")]
+async fn get_current_questions(data: web::Data<GameshowData>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    let questions = data.questions.read().await;
+    HttpResponse::Ok().header("Content-Disposition", "attachment; filename=\"questions.json\"").json(&(*questions))
+}
+
+//give a player money, minus value to remove money
+#[derive(Serialize, Deserialize)]
+struct GiveMoneyData
+{
+    name: String,
+    money: i64,
+}
+#[post("/api/giveMoney")]
+async fn give_money_post(data: web::Data<GameshowData>, params: web::Json<GiveMoneyData>) -> impl Responder
+{
+    give_money(data, &params).await
+}
+async fn give_money(data: web::Data<GameshowData>, params: &GiveMoneyData) -> HttpResponse
+{
+    let mut access = data.player_data.write().await;
+    let player_index = data.player_index.read().await;
+    let player = match (*player_index).get(&params.name)
+    {
+        Some(&i) => &mut (*access)[i],
+        None => { return HttpResponse::BadRequest().body("Player name was not found!"); },
+    };
+    
+    let strict_give_money = data.config.strict_give_money;
+    if strict_give_money && params.money < -player.money
+    {
+        return HttpResponse::BadRequest().body("Deduction is bigger than the player's money!");
+    }
+    player.money = apply_money_limits(&data.config, player.money.saturating_add(params.money));
+    data.roster_version.fetch_add(1, Ordering::Relaxed);
+    HttpResponse::Ok().json(GiveMoneyData {name: player.name.clone(), money: player.money})
+}
+
+//set a player's money to an absolute value (clamped at the money floor)
+#[derive(Serialize, Deserialize)]
+struct SetMoneyData
+{
+    name: String,
+    money: i64,
+}
+#[post("/api/setMoney")]
+async fn set_money(data: web::Data<GameshowData>, params: web::Json<SetMoneyData>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    let mut access = data.player_data.write().await;
+    let player_index = data.player_index.read().await;
+    let player = match (*player_index).get(&params.name)
+    {
+        Some(&i) => &mut (*access)[i],
+        None => { return HttpResponse::BadRequest().body("Player name was not found!"); },
+    };
+    
+    player.money = apply_money_limits(&data.config, params.money);
+    data.roster_version.fetch_add(1, Ordering::Relaxed);
+    HttpResponse::Ok().json(SetMoneyData {name: player.name.clone(), money: player.money})
+}
+
+//set a player's number of available jokers
+#[derive(Serialize, Deserialize)]
+struct SetJokersData
+{
+    name: String,
+    jokers: usize,
+}
+#[post("/api/setJokers")]
+async fn set_jokers(data: web::Data<GameshowData>, params: web::Json<SetJokersData>) -> impl Responder
+{
+    let mut access = data.player_data.write().await;
+    let player_index = data.player_index.read().await;
+    let player = match (*player_index).get(&params.name)
+    {
+        Some(&i) => &mut (*access)[i],
+        None => { return HttpResponse::BadRequest().body("Player name was not found!"); },
+    };
+    
+    player.jokers = params.jokers;
+    data.roster_version.fetch_add(1, Ordering::Relaxed);
+    HttpResponse::Ok().json(SetJokersData {name: player.name.clone(), jokers: player.jokers})
+}
+
+//set or change the number of available jokers of all players at once
+#[derive(Serialize, Deserialize)]
+struct SetJokersAllData
+{
+    jokers: Option<usize>, //absolute number of jokers to set for everyone
+    delta: Option<i64>, //number of jokers to add (or remove if negative) for everyone
+}
+#[post("/api/setJokersAll")]
+async fn set_jokers_all(data: web::Data<GameshowData>, params: web::Json<SetJokersAllData>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    if params.jokers.is_some() == params.delta.is_some()
+    {
+        return HttpResponse::BadRequest().body("Exactly one of jokers and delta must be given!");
+    }
+
+    let mut access = data.player_data.write().await;
+    for player in (*access).iter_mut()
+    {
+        if let Some(jokers) = params.jokers
+        {
+            player.jokers = jokers;
+        }
+        else if let Some(delta) = params.delta
+        { //clamp at zero jokers
+            let jokers = player.jokers as i64 + delta;
+            player.jokers = if jokers < 0 { 0 } else { jokers as usize };
+        }
+    }
+    data.roster_version.fetch_add(1, Ordering::Relaxed);
+
+    let result: Vec<SetJokersData> = (*access).iter().map(|player| SetJokersData {name: player.name.clone(), jokers: player.jokers}).collect();
+    HttpResponse::Ok().json(result)
+}
+
+//disable a player temporarily (or enable again), instead of kicking and losing the score
+#[derive(Serialize, Deserialize)]
+struct SetPlayerDisabledData
+{
+    name: String,
+    disabled: bool,
+}
+#[post("/api/setPlayerDisabled")]
+async fn set_player_disabled(data: web::Data<GameshowData>, params: web::Json<SetPlayerDisabledData>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    let mut access = data.player_data.write().await;
+    let player_index = data.player_index.read().await;
+    let player = match (*player_index).get(&params.name)
+    {
+        Some(&i) => &mut (*access)[i],
+        None => { return HttpResponse::BadRequest().body("Player name was not found!"); },
+    };
+    
+    player.disabled = params.disabled;
+    data.roster_version.fetch_add(1, Ordering::Relaxed);
+    HttpResponse::Ok().json(SetPlayerDisabledData {name: player.name.clone(), disabled: player.disabled})
+}
+
+//kick a player
+#[derive(Serialize, Deserialize)]
+struct KickPlayerData
+{
+    name: String,
+    #[serde(default)]
+    hard: bool, //remove immediately, even with KICK_GRACE_SECS
+}
+#[get("/api/kickPlayer")]
+async fn kick_player_get(data: web::Data<GameshowData>, params: web::Query<KickPlayerData>) -> impl Responder
+{
+    kick_player(data, &params).await
+}
+#[post("/api/kickPlayer")]
+async fn kick_player_post(data: web::Data<GameshowData>, params: web::Json<KickPlayerData>) -> impl Responder
+{
+    kick_player(data, &params).await
+}
+async fn kick_player(data: web::Data<GameshowData>, params: &KickPlayerData) -> HttpResponse
+{
+    let mut access = data.player_data.write().await;
+    
+    //soft kick: the player is only removed after the grace, if not reconnecting before
+    let kick_grace_secs = data.config.kick_grace_secs;
+    if kick_grace_secs > 0 && !params.hard
+    {
+        let player_index = data.player_index.read().await;
+        return match (*player_index).get(&params.name)
+        {
+            Some(&i) => {
+                (*access)[i].pending_removal = Some(Instant::now() + Duration::from_secs(kick_grace_secs));
+                data.roster_version.fetch_add(1, Ordering::Relaxed);
+                HttpResponse::Ok().finish()
+            },
+            None => HttpResponse::BadRequest().body("Player name was not found!"),
+        };
+    }
+    
+    let len = (*access).len();
+    (*access).retain(|player| player.name != params.name);
+    if (*access).len() == len
+    { //player was not found
+        return HttpResponse::BadRequest().body("Player name was not found!");
+    }
+    *data.player_index.write().await = build_player_index(&access);
+    data.player_pins.write().await.remove(&params.name);
+    data.roster_version.fetch_add(1, Ordering::Relaxed);
+    let mut events = data.write_events().await;
+    push_event(&mut events, "PlayerLeft", EventType::PlayerLeft(EventPlayerLeft { name: params.name.clone() }));
+    
+    HttpResponse::Ok().finish()
+}
+
+//show a message of the host on all screens (without control characters, cut at ANNOUNCEMENT_MAX_LENGTH)
+#[derive(Serialize, Deserialize)]
+struct AnnounceData
+{
+    message: String,
+}
+#[post("/api/announce")]
+async fn announce(data: web::Data<GameshowData>, params: web::Json<AnnounceData>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    let announcement_max_length = data.config.announcement_max_length;
+    let message: String = params.message.chars().filter(|c| !c.is_control()).take(announcement_max_length).collect();
+    let message = String::from(message.trim());
+    if message.is_empty()
+    {
+        return HttpResponse::BadRequest().body("Empty message is not allowed!");
+    }
+    let mut events = data.write_events().await;
+    push_event(&mut events, "Announcement", EventType::Announcement(EventAnnouncement { message: message.clone() }));
+    
+    HttpResponse::Ok().json(AnnounceData { message })
+}
+
+//reveal a player's answer in the results (for REVEAL_MODE OneByOne)
+#[derive(Serialize, Deserialize)]
+struct RevealPlayerData
+{
+    name: String,
+}
+#[post("/api/revealPlayer")]
+async fn reveal_player(data: web::Data<GameshowData>, params: web::Query<RevealPlayerData>) -> impl Responder
+{
+    //ensure current question state is results, else return not acceptable
+    let question_state = data.current_question_state.read().await;
+    if !is_action_allowed(*question_state, "revealPlayer")
+    {
+        return HttpResponse::NotAcceptable().body("QuestionState is not Results(false)!");
+    }
+    
+    let question_id = data.current_question.load(Ordering::Relaxed);
+    let questions = data.questions.read().await;
+    if question_id < 1 || question_id > (*questions).len()
+    {
+        return HttpResponse::NotAcceptable().body("There are no results of a question to reveal!");
+    }
+    let question = &(*questions)[question_id - 1];
+    
+    let access = data.player_data.read().await;
+    let player_index = data.player_index.read().await;
+    let player = match (*player_index).get(&params.name)
+    {
+        Some(&i) => &(*access)[i],
+        None => { return HttpResponse::BadRequest().body("Player name was not found!"); },
+    };
+    let event_data = EventRevealPlayer { name: player.name.clone(), answer: player.answer, answers_selected: player.answers_selected.clone(),
+        estimate_f: player.estimate_f, correct: answered_correctly(question, player) };
+    let mut events = data.write_events().await;
+    push_event(&mut events, "RevealPlayer", EventType::RevealPlayer(event_data));
+    
+    //the round summary was held back until the last player is revealed
+    if data.config.reveal_mode == "OneByOne" && unrevealed_players(&data.config, &events, *question_state).is_empty()
+    {
+        let round_summary = match (*events).iter().rposition(|event| event.event_name == "ShowResults")
+        {
+            Some(position) => match &(*events)[position].event
+            {
+                EventType::ShowResults(results) if !(*events)[position ..].iter().any(|event| event.event_name == "RoundSummary") =>
+                    Some(round_summary_event(results.question_index, question, &results.previous_player_data, &results.player_data)),
+                _ => None,
+            },
+            None => None,
+        };
+        if let Some(round_summary) = round_summary
+        {
+            push_event(&mut events, "RoundSummary", round_summary);
+        }
+    }
+    
+    HttpResponse::Ok().finish()
+}
+
+//give the bonus money to the player who answered the just scored question correctly first (once per question)
+#[derive(Serialize, Deserialize)]
+struct BonusFastestData
+{
+    name: String,
+    money: i64,
+}
+#[post("/api/bonusFastest")]
+async fn bonus_fastest(data: web::Data<GameshowData>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    //ensure current question state is results, else return not acceptable
+    let question_state = data.current_question_state.read().await;
+    if !is_action_allowed(*question_state, "bonusFastest")
+    {
+        return HttpResponse::NotAcceptable().body("QuestionState is not Results(false)!");
+    }
+    
+    let question_id = data.current_question.load(Ordering::Relaxed);
+    let questions = data.questions.read().await;
+    if question_id < 1 || question_id > (*questions).len()
+    {
+        return HttpResponse::NotAcceptable().body("There are no results of a question to give a bonus for!");
+    }
+    if data.fastest_bonus_question.load(Ordering::Relaxed) == question_id
+    {
+        return HttpResponse::Conflict().body("The bonus was already given for this question!");
+    }
+    let question = &(*questions)[question_id - 1];
+    
+    let mut access = data.player_data.write().await;
+    let fastest = (*access).iter_mut()
+        .filter(|player| player.answered_at.is_some() && answered_correctly(question, player))
+        .min_by_key(|player| player.answered_at);
+    let player = match fastest
+    {
+        Some(player) => player,
+        None => { return HttpResponse::NotFound().body("No player answered correctly!"); },
+    };
+    let bonus_fastest_money = data.config.bonus_fastest_money;
+    player.money = apply_money_limits(&data.config, player.money.saturating_add(bonus_fastest_money));
+    data.fastest_bonus_question.store(question_id, Ordering::Relaxed);
+    data.roster_version.fetch_add(1, Ordering::Relaxed);
+    
+    HttpResponse::Ok().json(BonusFastestData { name: player.name.clone(), money: player.money })
+}
+
+//spin the bonus wheel once after a question the player gained money in, the gain is multiplied by a random BONUS_WHEEL multiplier
+#[derive(Serialize, Deserialize)]
+struct SpinBonusData
+{
+    name: String,
+    pin: Option<String>,
+}
+#[derive(Serialize, Deserialize)]
+struct SpinBonusResult
+{
+    multiplier: f64,
+    money: i64, //total money after the spin
+}
+#[get("/api/spinBonus")]
+async fn spin_bonus(data: web::Data<GameshowData>, params: web::Query<SpinBonusData>) -> impl Responder
+{
+    //ensure current question state is results, else return not acceptable
+    let question_state = data.current_question_state.read().await;
+    if !is_action_allowed(*question_state, "spinBonus")
+    {
+        return HttpResponse::NotAcceptable().body("QuestionState is not Results(false)!");
+    }
+    if data.config.bonus_wheel.is_empty()
+    {
+        return HttpResponse::NotAcceptable().body("There is no bonus wheel (BONUS_WHEEL is empty)!");
+    }
+    
+    let mut access = data.player_data.write().await;
+    let player_index = data.player_index.read().await;
+    let player = match (*player_index).get(&params.name)
+    {
+        Some(&i) => &mut (*access)[i],
+        None => { return HttpResponse::BadRequest().body("Player name was not found!"); },
+    };
+    let pins = data.player_pins.read().await;
+    if (*pins).get(&params.name).is_some_and(|pin| params.pin.as_ref() != Some(pin))
+    {
+        return HttpResponse::Forbidden().body("Wrong PIN for this player!");
+    }
+    if player.bonus_spun
+    {
+        return HttpResponse::Conflict().body("The player already spun the bonus wheel after this question!");
+    }
+    //the gain of the player in the results of the current game's last question
+    let mut events = data.write_events().await;
+    let game_start = (*events).iter().rposition(|event| event.event_name == "QuestionsLoaded" || event.event_name == "NewGameStarted")
+        .map_or(0, |position| position + 1);
+    let gain = (*events)[game_start ..].iter().rev().find_map(|event| match &event.event
+        {
+            EventType::ShowResults(results) => Some(results),
+            _ => None,
+        })
+        .and_then(|results| {
+            let previous = results.previous_player_data.iter().find(|previous| previous.name == params.name)?;
+            let current = results.player_data.iter().find(|current| current.name == params.name)?;
+            Some(current.money - previous.money)
+        })
+        .unwrap_or(0);
+    if gain <= 0
+    {
+        return HttpResponse::NotAcceptable().body("The player did not gain money in the last question!");
+    }
+    
+    let mut rng = data.rng.lock().await;
+    let multiplier = *data.config.bonus_wheel.choose(&mut *rng).unwrap();
+    let bonus = (gain as f64 * multiplier).round() as i64 - gain;
+    player.money = apply_money_limits(&data.config, player.money.saturating_add(bonus));
+    player.bonus_spun = true;
+    data.roster_version.fetch_add(1, Ordering::Relaxed);
+    let event_data = EventBonusSpin { name: player.name.clone(), multiplier };
+    push_event(&mut events, "BonusSpin", EventType::BonusSpin(event_data));
+    
+    HttpResponse::Ok().json(SpinBonusResult { multiplier, money: player.money })
+}
+
+//fix the correct answer of a loaded question (e.g. a wrong key noticed in the results), use recomputeResults to score it again
+#[derive(Serialize, Deserialize)]
+struct FixCorrectAnswerData
+{
+    number: usize,
+    correct_answer: usize,
+    correct_answer_f: Option<f64>, //decimal correct answer for estimation questions
+}
+#[post("/api/fixCorrectAnswer")]
+async fn fix_correct_answer(data: web::Data<GameshowData>, params: web::Json<FixCorrectAnswerData>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    //ensure current question state is results or ended game, else return not acceptable
+    let question_state = data.current_question_state.read().await;
+    if !is_action_allowed(*question_state, "fixCorrectAnswer")
+    {
+        return HttpResponse::NotAcceptable().body("QuestionState is not Results(false) or GameEnding!");
+    }
+    
+    let mut questions = data.questions.write().await;
+    if params.number < 1 || params.number > (*questions).len()
+    {
+        return HttpResponse::BadRequest().body("Number is not a valid question ID (must be 1 - len(questions))!");
+    }
+    let question = &mut (*questions)[params.number - 1];
+    if question.question_type == QuestionType::EstimationQuestion
+    {
+        if params.correct_answer_f.is_some_and(|correct_answer_f| !correct_answer_f.is_finite())
+        {
+            return HttpResponse::BadRequest().body("correct_answer_f must be a finite number!");
+        }
+        question.correct_answer_f = params.correct_answer_f;
+    }
+    else if params.correct_answer < 1 || params.correct_answer > question.answers.len()
+    {
+        return HttpResponse::BadRequest().body("correct_answer is not a valid answer ID (must be 1 - len(answers))!");
+    }
+    question.correct_answer = params.correct_answer;
+    
+    HttpResponse::Ok().finish()
+}
+
+//score the just completed question again (e.g. after fixing its correct answer), starting from the money before its results
+#[post("/api/recomputeResults")]
+async fn recompute_results(data: web::Data<GameshowData>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    {
+        //ensure current question state is results, else return not acceptable
+        let mut question_state = data.current_question_state.write().await;
+        if !is_action_allowed(*question_state, "recomputeResults")
+        {
+            return HttpResponse::NotAcceptable().body("QuestionState is not Results(false)!");
+        }
+        let question_id = data.current_question.load(Ordering::Relaxed);
+        let questions = data.questions.read().await;
+        if question_id < 1 || question_id > (*questions).len()
+        {
+            return HttpResponse::NotAcceptable().body("There are no results of a question to recompute!");
+        }
+        
+        //the last results must belong to the current question
+        let mut access = data.player_data.write().await;
+        let events = data.game_events.read().await;
+        let baseline = match (*events).iter().rposition(|event| event.event_name == "ShowResults")
+        {
+            Some(position) => match &(*events)[position].event
+            {
+                EventType::ShowResults(results) if results.question_index == question_id => &results.previous_player_data,
+                _ => { return HttpResponse::Conflict().body("The last results do not belong to the current question!"); },
+            },
+            None => { return HttpResponse::Conflict().body("The last results do not belong to the current question!"); },
+        };
+        //restore everything the scoring changed, the answers are still there
+        for player in (*access).iter_mut()
+        {
+            if let Some(previous) = baseline.iter().find(|previous| previous.name == player.name)
+            {
+                player.money = previous.money;
+                player.lives = previous.lives;
+                player.eliminated = previous.eliminated;
+                player.elimination_order = previous.elimination_order;
+                player.correct_count = previous.correct_count;
+                player.answered_count = previous.answered_count;
+                player.streak = previous.streak;
+                player.missed_rounds = previous.missed_rounds;
+                //a spun bonus is discarded with the old results, so the wheel may be spun again on the new ones
+                player.bonus_spun = false;
+            }
+        }
+        data.roster_version.fetch_add(1, Ordering::Relaxed);
+        *question_state = match (*questions)[question_id - 1].question_type
+        {
+            QuestionType::NormalQuestion => QuestionState::NormalQAnswering(true),
+            QuestionType::BettingQuestion => QuestionState::BettingQAnswering(true),
+            QuestionType::EstimationQuestion => QuestionState::EstimationQAnswering(true),
+            QuestionType::VersusQuestion => QuestionState::VersusQAnswering(true),
+            QuestionType::MultiSelectQuestion => QuestionState::MultiSelectQAnswering(true),
+        };
+    }
+    //score again with a new ShowResults event, deliberately immediate instead of waiting for the ticker
+    //(the host expects the new results when the request returns)
+    check_state_add_events(data.clone()).await;
+    
+    HttpResponse::Ok().finish()
+}
+
+//end the game after the last question (only with CONFIRM_GAME_END)
+#[post("/api/confirmGameEnd")]
+async fn confirm_game_end(data: web::Data<GameshowData>) -> impl Responder
+{
+    //ensure current question state is awaiting the game end, else return not acceptable
+    let mut question_state = data.current_question_state.write().await;
+    if !is_action_allowed(*question_state, "confirmGameEnd")
+    {
+        return HttpResponse::NotAcceptable().body("QuestionState is not AwaitingGameEnd!");
+    }
+    //the host's confirmation ends the game deliberately immediately instead of leaving it to the ticker
+    let access = data.player_data.read().await;
+    let mut events = data.write_events().await;
+    push_game_ending(&data, &mut events, &access).await;
+    *question_state = QuestionState::GameEnding;
+    data.state_dirty.store(true, Ordering::Relaxed);
+    
+    HttpResponse::Ok().finish()
+}
+
+//start a new game with the same questions and players after the game ended
+#[derive(Serialize, Deserialize)]
+struct NewGameData
+{
+    #[serde(default)]
+    reset_players: bool, //also reset the players' money and jokers to INITIAL_MONEY and INITIAL_JOKERS
+}
+#[post("/api/newGame")]
+async fn new_game(data: web::Data<GameshowData>, params: web::Json<NewGameData>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    //ensure current question state is the ended game, else return not acceptable; hold the lock until finished this time
+    let mut question_state = data.current_question_state.write().await;
+    if !is_action_allowed(*question_state, "newGame")
+    {
+        return HttpResponse::NotAcceptable().body("QuestionState is not GameEnding!");
+    }
+    *data.answers_ready_at.lock().await = None;
+    *data.bets_locked_until.lock().await = None;
+    let questions = data.questions.read().await;
+    data.current_question.store(0, Ordering::Relaxed);
+    data.questions_played.store(0, Ordering::Relaxed);
+    data.jokers_used.store(0, Ordering::Relaxed);
+    data.fastest_bonus_question.store(0, Ordering::Relaxed);
+    //everything of the last game is reset for all players, except the money and jokers if not wanted
+    let time_budget_ms = data.config.time_budget_ms;
+    let mut access = data.player_data.write().await;
+    for player in (*access).iter_mut()
+    {
+        if params.reset_players
+        {
+            player.money = data.config.initial_money;
+            player.jokers = data.config.initial_jokers;
+        }
+        player.money_bet = 0;
+        player.vs_player = "".to_owned();
+        player.vs_players = Vec::new();
+        player.answer = 0;
+        player.answers_selected = Vec::new();
+        player.estimate_f = None;
+        player.missed_rounds = 0;
+        player.lives = data.config.initial_lives;
+        player.eliminated = false;
+        player.elimination_order = None;
+        player.correct_count = 0;
+        player.answered_count = 0;
+        player.streak = 0;
+        player.time_budget_ms = if time_budget_ms > 0 { Some(time_budget_ms) } else { None };
+        player.answered_at = None;
+        player.removed_answers = Vec::new();
+        player.bonus_spun = false;
+    }
+    data.roster_version.fetch_add(1, Ordering::Relaxed);
+    *question_state = QuestionState::Results(false);
+    let mut events = data.write_events().await;
+    let event_data = EventNewGameStarted { num_questions: (*questions).len(), reset_players: params.reset_players };
+    push_event(&mut events, "NewGameStarted", EventType::NewGameStarted(event_data));
+    data.transitions.lock().await.clear();
+    data.state_dirty.store(true, Ordering::Relaxed);
+    
+    HttpResponse::Ok().finish()
+}
+
+//end the game immediately and skip the remaining questions
+#[post("/api/endGame")]
+async fn end_game(data: web::Data<GameshowData>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    //score the answers of a running question first if wanted, by letting it transition to the results
+    let end_game_scoring = data.config.end_game_scoring;
+    let score_answers;
+    {
+        let mut question_state = data.current_question_state.write().await;
+        if !is_action_allowed(*question_state, "endGame")
+        {
+            return HttpResponse::NotAcceptable().body("QuestionState is GameEnding already!");
+        }
+        if end_game_scoring
+        {
+            set_answering_ready(&mut question_state);
+        }
+        score_answers = end_game_scoring && matches!(*question_state, QuestionState::NormalQAnswering(true) |
+            QuestionState::BettingQAnswering(true) | QuestionState::EstimationQAnswering(true) |
+            QuestionState::VersusQAnswering(true) | QuestionState::MultiSelectQAnswering(true));
+    }
+    if score_answers
+    { //deliberately immediate instead of waiting for the ticker, the results must exist before the game ending below
+        check_state_add_events(data.clone()).await;
+    }
+    
+    let mut question_state = data.current_question_state.write().await;
+    if *question_state == QuestionState::GameEnding
+    {
+        return HttpResponse::NotAcceptable().body("QuestionState is GameEnding already!");
+    }
+    let access = data.player_data.read().await;
+    let mut events = data.write_events().await;
+    push_game_ending(&data, &mut events, &access).await;
+    *question_state = QuestionState::GameEnding;
+    data.state_dirty.store(true, Ordering::Relaxed);
+    
+    HttpResponse::Ok().finish()
+}
+
+//activate next question, will fail if current question was not finished
+#[get("/api/activateNextQuestion")]
+async fn activate_next_question_get(data: web::Data<GameshowData>) -> impl Responder
+{
+    activate_next_question(data).await
+}
+async fn activate_next_question(data: web::Data<GameshowData>) -> HttpResponse
+{
+    //check if game state is ready for next question
+    let mut access = data.current_question_state.write().await;
+    if *access == QuestionState::Results(true)
+    { //already activated, but not transitioned yet; do not trigger again (e.g. on double click)
+        return HttpResponse::Conflict().body("Next question is already being activated!");
+    }
+    if data.questions.read().await.is_empty()
+    {
+        return HttpResponse::NotAcceptable().body("No questions loaded!");
+    }
+    if is_action_allowed(*access, "activateNextQuestion")
+    { //indicate possible transition to next question for automatic switch
+        *access = QuestionState::Results(true);
+        return HttpResponse::Ok().finish();
+    }
+    else
+    {
+        return HttpResponse::NotAcceptable().body("QuestionState is not Results! => Not ready for next question!");
+    }
+}
+
+//show the question after the category teaser
+#[post("/api/revealQuestion")]
+async fn reveal_question_post(data: web::Data<GameshowData>) -> impl Responder
+{
+    reveal_question(data).await
+}
+async fn reveal_question(data: web::Data<GameshowData>) -> HttpResponse
+{
+    //ensure current question state is the category teaser, else return not acceptable
+    let mut question_state = data.current_question_state.write().await;
+    if !is_action_allowed(*question_state, "revealQuestion")
+    {
+        return HttpResponse::NotAcceptable().body("QuestionState is not CategoryTeaser(false)!");
+    }
+    *question_state = QuestionState::CategoryTeaser(true);
+    HttpResponse::Ok().finish()
+}
+
+//force end of betting and activate question answering
+#[get("/api/forceQuestionAnswering")]
+async fn force_question_answering_get(data: web::Data<GameshowData>) -> impl Responder
+{
+    force_question_answering(data).await
+}
+async fn force_question_answering(data: web::Data<GameshowData>) -> HttpResponse
+{
+    //ensure current question state is betting or selecting, else return not acceptable
+    let mut question_state = data.current_question_state.write().await;
+    if !is_action_allowed(*question_state, "forceQuestionAnswering")
+    {
+        return HttpResponse::NotAcceptable().body("QuestionState is not Betting(false) or Selecting(false)!");
+    }
+    force_ready(&data, &mut question_state).await;
+    HttpResponse::Ok().finish()
+}
+
+//force the current betting, selecting or answering to be ready (players without a bet get the DEFAULT_BET),
+//shared by forceQuestionAnswering, forceQuestionResults and the GLOBAL_QUESTION_TIMEOUT_SECS
+async fn force_ready(data: &GameshowData, question_state: &mut QuestionState)
+{
+    match *question_state
+    {
+        QuestionState::BettingQBetting(_) => {
+            //skip the remaining delay if the bets are locked already
+            {
+                let mut locked_until = data.bets_locked_until.lock().await;
+                if locked_until.is_some()
+                {
+                    *locked_until = Some(Instant::now());
+                }
+            }
+            //players without a bet get the default bet (at most their money)
+            let default_bet = data.config.default_bet;
+            let mut access = data.player_data.write().await;
+            let mut players = Vec::new();
+            for player in (*access).iter_mut().filter(|player| player.money_bet < 1 && participates_this_round(&data.config, player))
+            {
+                player.money_bet = if default_bet > player.money { player.money } else { default_bet.max(0) };
+                players.push(player.name.clone());
+            }
+            if !players.is_empty()
+            {
+                data.roster_version.fetch_add(1, Ordering::Relaxed);
+                let mut events = data.write_events().await;
+                push_event(&mut events, "DefaultBetApplied", EventType::DefaultBetApplied(EventDefaultBetApplied { players,
+                    money_bet: default_bet.max(0) }));
+            }
+            *question_state = QuestionState::BettingQBetting(true);
+        },
+        QuestionState::VersusQSelecting(_) => { *question_state = QuestionState::VersusQSelecting(true); },
+        _ => { set_answering_ready(question_state); },
+    }
+}
+
+//force end of question answering and show results
+#[get("/api/forceQuestionResults")]
+async fn force_question_results_get(data: web::Data<GameshowData>) -> impl Responder
+{
+    force_question_results(data).await
+}
+async fn force_question_results(data: web::Data<GameshowData>) -> HttpResponse
+{
+    //ensure current question state is answering, else return not acceptable
+    let mut question_state = data.current_question_state.write().await;
+    if !is_action_allowed(*question_state, "forceQuestionResults")
+    {
+        return HttpResponse::NotAcceptable().body("QuestionState is not *Answering(false)!");
+    }
+    force_ready(&data, &mut question_state).await;
+    HttpResponse::Ok().finish()
+}
+
+//set the next question (only possible, when currently in results state)
+#[derive(Serialize, Deserialize)]
+struct SetNextQuestionData
+{
+    number: usize,
+}
+#[get("/api/setNextQuestion")]
+async fn set_next_question(data: web::Data<GameshowData>, params: web::Query<SetNextQuestionData>) -> impl Responder
+{
+    //ensure current question state is results or ended game, else return not acceptable; hold the lock until finished this time
+    let mut question_state = data.current_question_state.write().await;
+    if !is_action_allowed(*question_state, "setNextQuestion")
+    {
+        return HttpResponse::NotAcceptable().body("QuestionState is not Results(false) or GameEnding!");
+    }
+    
+    let questions = data.questions.read().await;
+    if params.number < 1 || params.number > (*questions).len()
+    {
+        return HttpResponse::BadRequest().body("Number is not a valid question ID (must be 1 - len(questions))!");
+    }
+    else
+    {
+        let question_id = data.current_question.swap(params.number - 1, Ordering::Relaxed);
+        *question_state = QuestionState::Results(false);
+        let mut events = data.write_events().await;
+        let event_data = EventQuestionQueueChanged { current: params.number - 1, total: (*questions).len() };
+        push_event(&mut events, "QuestionQueueChanged", EventType::QuestionQueueChanged(event_data));
+        return HttpResponse::Ok().body(question_id.to_string());
+    }
+}
+
+//shuffle the questions not played yet, the played ones and the current question number stay in place
+#[post("/api/shuffleRemaining")]
+async fn shuffle_remaining(data: web::Data<GameshowData>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    //ensure current question state is results or ended game, else return not acceptable; hold the lock until finished this time
+    let question_state = data.current_question_state.write().await;
+    if !is_action_allowed(*question_state, "shuffleRemaining")
+    {
+        return HttpResponse::NotAcceptable().body("QuestionState is not Results(false) or GameEnding!");
+    }
+    
+    let mut questions = data.questions.write().await;
+    let current = data.current_question.load(Ordering::Relaxed).min((*questions).len());
+    let mut events = data.write_events().await;
+    let mut rng = data.rng.lock().await;
+    (*questions)[current..].shuffle(&mut *rng);
+    let event_data = EventQuestionQueueChanged { current, total: (*questions).len() };
+    push_event(&mut events, "QuestionQueueChanged", EventType::QuestionQueueChanged(event_data));
+    HttpResponse::Ok().body(((*questions).len() - current).to_string())
+}
+
+//append the given question and begin it immediately, skipping the rest of the queue
+#[post("/api/startQuestion")]
+async fn start_question(data: web::Data<GameshowData>, params: web::Json<Question>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    {
+        //ensure current question state is results or ended game, else return not acceptable; hold the lock until prepared
+        let mut question_state = data.current_question_state.write().await;
+        if !is_action_allowed(*question_state, "startQuestion")
+        {
+            return HttpResponse::NotAcceptable().body("QuestionState is not Results(false) or GameEnding!");
+        }
+        let question = params.into_inner();
+        if let Err(error) = validate_questions(std::slice::from_ref(&question))
+        {
+            return HttpResponse::BadRequest().body(error);
+        }
+        
+        let mut questions = data.questions.write().await;
+        (*questions).push(question);
+        //point to the question before it, so that the transition to the next question begins it
+        let current = (*questions).len() - 1;
+        data.current_question.store(current, Ordering::Relaxed);
+        let mut events = data.write_events().await;
+        let event_data = EventQuestionQueueChanged { current, total: (*questions).len() };
+        push_event(&mut events, "QuestionQueueChanged", EventType::QuestionQueueChanged(event_data));
+        *question_state = QuestionState::Results(true);
+    }
+    //begin the question's betting, selecting or answering like any other question,
+    //deliberately immediate instead of waiting for the ticker (the response is the begun question's number)
+    check_state_add_events(data.clone()).await;
+    data.state_dirty.store(true, Ordering::Relaxed);
+    
+    HttpResponse::Ok().body(data.current_question.load(Ordering::Relaxed).to_string())
+}
+
+//load questions from a the given filename
+#[derive(Serialize, Deserialize)]
+struct LoadQuestions
+{
+    filename: String,
+}
+#[post("/api/loadQuestions")]
+async fn load_questions(data: web::Data<GameshowData>, params: web::Json<LoadQuestions>) -> impl Responder
+{
+    //ensure current question state is results or ended game, else return not acceptable; hold the lock until finished this time
+    let mut question_state = data.current_question_state.write().await;
+    if !is_action_allowed(*question_state, "loadQuestions")
+    {
+        return HttpResponse::NotAcceptable().body("QuestionState is not Results(false) or GameEnding!");
+    }
+    
+    match read_questions(String::from("./Questions/") + &params.filename).await
+    {
+        Ok(new_questions) => {
+            let count = set_questions(&data, &mut question_state, new_questions).await;
+            HttpResponse::Ok().body(count.to_string())
+        },
+        Err(err) => HttpResponse::BadRequest().body(format!("Question file could not be loaded: {}!", err)),
+    }
+}
+
+//load questions from the given CSV filename (columns: type, category, question, answer 1 .. answer N, correct)
+#[post("/api/loadQuestionsCsv")]
+async fn load_questions_csv(data: web::Data<GameshowData>, params: web::Json<LoadQuestions>) -> impl Responder
+{
+    //ensure current question state is results or ended game, else return not acceptable; hold the lock until finished this time
+    let mut question_state = data.current_question_state.write().await;
+    if !is_action_allowed(*question_state, "loadQuestions")
+    {
+        return HttpResponse::NotAcceptable().body("QuestionState is not Results(false) or GameEnding!");
+    }
+    
+    match read_questions_csv(String::from("./Questions/") + &params.filename).await
+    {
+        Ok(new_questions) => {
+            let count = set_questions(&data, &mut question_state, new_questions).await;
+            HttpResponse::Ok().body(count.to_string())
+        },
+        Err(err) => HttpResponse::BadRequest().body(format!("Question file could not be loaded: {}!", err)),
+    }
+}
+
+//load only the questions having all of the given tags from the given filename
+#[derive(Serialize, Deserialize)]
+struct LoadQuestionsFiltered
+{
+    filename: String,
+    tags: Vec<String>,
+}
+#[post("/api/loadQuestionsFiltered")]
+async fn load_questions_filtered(data: web::Data<GameshowData>, params: web::Json<LoadQuestionsFiltered>) -> impl Responder
+{
+    //ensure current question state is results or ended game, else return not acceptable; hold the lock until finished this time
+    let mut question_state = data.current_question_state.write().await;
+    if !is_action_allowed(*question_state, "loadQuestions")
+    {
+        return HttpResponse::NotAcceptable().body("QuestionState is not Results(false) or GameEnding!");
+    }
+    
+    let new_questions = match read_questions(String::from("./Questions/") + &params.filename).await
+    {
+        Ok(new_questions) => new_questions,
+        Err(err) => { return HttpResponse::BadRequest().body(format!("Question file could not be loaded: {}!", err)); },
+    };
+    let filtered: Vec<Question> = new_questions.into_iter()
+        .filter(|question| params.tags.iter().all(|tag| question.tags.contains(tag))).collect();
+    if filtered.is_empty()
+    {
+        return HttpResponse::BadRequest().body("No questions have all of the given tags!");
+    }
+    let count = set_questions(&data, &mut question_state, filtered).await;
+    HttpResponse::Ok().body(count.to_string())
+}
+
+//check a question file (or inline questions) for all problems without loading it
+#[derive(Serialize, Deserialize)]
+struct ValidateQuestionsData
+{
+    filename: Option<String>, //file name inside the questions folder
+    questions: Option<serde_json::Value>, //inline questions in the JSON format, used if there is no filename
+    format: Option<String>, //json or csv (default: csv for *.csv files, else json)
+}
+#[derive(Serialize, Deserialize)]
+struct QuestionError
+{
+    question: Option<usize>, //number of the question (None if the whole file is invalid)
+    reason: String,
+}
+#[derive(Serialize, Deserialize)]
+struct ValidationReport
+{
+    ok: bool,
+    errors: Vec<QuestionError>,
+}
+#[post("/api/validateQuestions")]
+async fn validate_questions_file(data: web::Data<GameshowData>, params: web::Json<ValidateQuestionsData>, req: HttpRequest) -> impl Responder
+{
+    if let Err(response) = check_admin_token(&data.config, &req)
+    {
+        return response;
+    }
+    //only files inside the questions folder can be checked
+    if params.filename.as_ref().is_some_and(|filename| filename.contains("..") || filename.contains('/') || filename.contains('\\'))
+    {
+        return HttpResponse::BadRequest().body("filename must not contain .. or path separators!");
+    }
+    let csv = match params.format.as_deref()
+    {
+        Some("csv") => true,
+        Some("json") => false,
+        Some(_) => { return HttpResponse::BadRequest().body("format must be json or csv!"); },
+        None => params.filename.as_ref().is_some_and(|filename| filename.ends_with(".csv")),
+    };
+    let parsed: Result<Vec<Question>, String> = match (&params.filename, &params.questions)
+    {
+        (Some(filename), _) if csv => parse_questions_csv(String::from("./Questions/") + filename).map_err(|err| err.to_string()),
+        (Some(filename), _) => match fs::read_to_string(String::from("./Questions/") + filename)
+        {
+            Ok(json_string) => serde_json::from_str(&json_string).map_err(|err| err.to_string()),
+            Err(err) => Err(format!("Question file could not be read: {}!", err)),
+        },
+        (None, Some(questions)) => serde_json::from_value(questions.clone()).map_err(|err| err.to_string()),
+        (None, None) => { return HttpResponse::BadRequest().body("filename or questions is required!"); },
+    };
+    let errors = match parsed
+    {
+        Ok(questions) => questions.iter().enumerate()
+            .flat_map(|(i, question)| question_errors(question).into_iter().map(move |reason| QuestionError { question: Some(i + 1), reason }))
+            .collect(),
+        Err(err) => vec![QuestionError { question: None, reason: err }],
+    };
+    HttpResponse::Ok().json(ValidationReport { ok: errors.is_empty(), errors })
+}
+
+//replace the questions and start again before the first question, returns the number of questions
+async fn set_questions(data: &GameshowData, question_state: &mut QuestionState, new_questions: Vec<Question>) -> usize
+{
+    let mut questions = data.questions.write().await;
+    (*questions) = new_questions;
+    data.current_question.store(0, Ordering::Relaxed);
+    data.questions_played.store(0, Ordering::Relaxed);
+    data.jokers_used.store(0, Ordering::Relaxed);
+    //the answer statistics start again with the new questions
+    let mut access = data.player_data.write().await;
+    for player in (*access).iter_mut()
+    {
+        player.correct_count = 0;
+        player.answered_count = 0;
+    }
+    data.roster_version.fetch_add(1, Ordering::Relaxed);
+    *question_state = QuestionState::Results(false);
+    let mut events = data.write_events().await;
+    push_event(&mut events, "QuestionsLoaded", EventType::QuestionsLoaded(EventQuestionsLoaded { count: (*questions).len() }));
+    data.transitions.lock().await.clear();
+    (*questions).len()
+}
+
+//WebSocket channel for the host's actions: each JSON text frame {id: 0, command: "", params: {}} runs the REST endpoint
+//of the same name and is answered with an ack frame (the connection needs the ADMIN_TOKEN as token in the query)
+#[derive(Serialize, Deserialize)]
+struct HostCommandsConnectData
+{
+    token: Option<String>,
+}
+#[derive(Serialize, Deserialize)]
+struct HostCommand
+{
+    id: Option<u64>, //given back in the ack to match it
+    command: String,
+    #[serde(default)]
+    params: serde_json::Value,
+}
+#[derive(Serialize, Deserialize)]
+struct HostCommandAck
+{
+    id: Option<u64>,
+    command: String,
+    ok: bool,
+    status: u16, //the HTTP status the REST endpoint responds with
+    body: String, //the response body of the REST endpoint
+}
+#[get("/api/ws")]
+async fn host_commands(data: web::Data<GameshowData>, req: HttpRequest, params: web::Query<HostCommandsConnectData>, mut payload: web::Payload) -> HttpResponse
+{
+    if let Err(response) = check_token(&data.config, params.token.as_deref(), "token is missing!")
+    {
+        return response;
+    }
+    let mut response = match ws::handshake(req.head())
+    {
+        Ok(response) => response,
+        Err(err) => { return HttpResponse::from_error(err.into()); },
+    };
+    
+    //the frames are answered by a local task, the response streams the encoded answers
+    let (sender, receiver) = futures::channel::mpsc::unbounded::<Result<web::Bytes, actix_web::Error>>();
+    actix_web::rt::spawn(async move {
+        let mut codec = ws::Codec::new();
+        let mut buffer = web::BytesMut::new();
+        'connection: while let Some(Ok(chunk)) = payload.next().await
+        {
+            buffer.extend_from_slice(&chunk);
+            loop
+            {
+                let frame = match codec.decode(&mut buffer)
+                {
+                    Ok(Some(frame)) => frame,
+                    Ok(None) => break,
+                    Err(_) => { break 'connection; },
+                };
+                let (message, close) = match frame
+                {
+                    ws::Frame::Text(text) => (Some(ws::Message::Text(run_host_command(data.clone(), &text).await)), false),
+                    ws::Frame::Ping(message) => (Some(ws::Message::Pong(message)), false),
+                    ws::Frame::Close(reason) => (Some(ws::Message::Close(reason)), true),
+                    _ => (None, false),
+                };
+                if let Some(message) = message
+                {
+                    let mut encoded = web::BytesMut::new();
+                    if codec.encode(message, &mut encoded).is_err() || sender.unbounded_send(Ok(encoded.freeze())).is_err()
+                    {
+                        break 'connection;
+                    }
+                }
+                if close
+                {
+                    break 'connection;
+                }
+            }
+        }
+    });
+    response.streaming(receiver)
+}
+
+//the commands of the host's WebSocket channel (a whitelist, all other host actions are only REST endpoints)
+const HOST_COMMANDS: [&str; 5] = ["activateNextQuestion", "revealQuestion", "forceQuestionAnswering", "forceQuestionResults", "giveMoney"];
+
+//run a command frame of the host's WebSocket channel with the REST endpoint's logic, returns the ack frame
+async fn run_host_command(data: web::Data<GameshowData>, frame: &[u8]) -> String
+{
+    let command: HostCommand = match serde_json::from_slice(frame)
+    {
+        Ok(command) => command,
+        Err(err) => {
+            let ack = HostCommandAck { id: None, command: "".to_owned(), ok: false, status: StatusCode::BAD_REQUEST.as_u16(),
+                body: format!("Invalid command frame: {}!", err) };
+            return serde_json::to_string(&ack).unwrap_or_default();
+        },
+    };
+    let response = match command.command.as_str()
+    {
+        "activateNextQuestion" => activate_next_question(data).await,
+        "revealQuestion" => reveal_question(data).await,
+        "forceQuestionAnswering" => force_question_answering(data).await,
+        "forceQuestionResults" => force_question_results(data).await,
+        "giveMoney" => match serde_json::from_value(command.params.clone())
+        {
+            Ok(params) => give_money(data, &params).await,
+            Err(err) => HttpResponse::BadRequest().body(format!("Invalid params: {}!", err)),
+        },
+        _ => HttpResponse::NotFound().body(format!("Unknown command, the commands are: {}!", HOST_COMMANDS.join(", "))),
+    };
+    let body = match response.body().as_ref()
+    {
+        Some(Body::Bytes(bytes)) => String::from_utf8_lossy(bytes).into_owned(),
+        _ => String::new(),
+    };
+    let ack = HostCommandAck { id: command.id, command: command.command, ok: response.status().is_success(),
+        status: response.status().as_u16(), body };
+    serde_json::to_string(&ack).unwrap_or_default()
+}
+
+
+//fallback for unknown API endpoints, so that they do not end up at the static files
+#[derive(Serialize, Deserialize)]
+struct UnknownEndpointData
+{
+    error: String,
+    path: String,
+}
+async fn unknown_endpoint(req: HttpRequest) -> impl Responder
+{
+    HttpResponse::NotFound().json(UnknownEndpointData { error: String::from("UnknownEndpoint"), path: String::from(req.path()) })
+}
+
+//replace player names (and PINs) in a query string to keep them out of the logs
+fn scrub_query(query: &str) -> String
+{
+    query.split('&').map(|pair| {
+        match pair.split_once('=')
+        {
+            Some((key, _)) if key == "name" || key == "vs_player" || key == "pin" => format!("{}=***", key),
+            _ => String::from(pair),
+        }
+    }).collect::<Vec<String>>().join("&")
+}
+
+//structured log line of a request (the player names in the query are scrubbed)
+fn request_log_line(method: &str, path: &str, query: &str, status: StatusCode, duration: Duration) -> String
+{
+    format!("method={} path={} query=\"{}\" status={} duration_ms={:.3}", method, path, scrub_query(query),
+        status.as_u16(), duration.as_secs_f64() * 1000.0)
+}
+
+//single background ticker that owns all state transitions, so that they do not depend on clients polling
+//(only recomputeResults, confirmGameEnd, endGame and startQuestion transition immediately on purpose, their response depends on the result)
+fn spawn_state_ticker(data: web::Data<GameshowData>)
+{
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(Duration::from_millis(200));
+        loop
+        {
+            interval.tick().await;
+            check_state_add_events(data.clone()).await;
+            remove_kicked_players(&data).await;
+        }
+    });
+}
+
+//register the API services, the JSON 404 for unknown API paths and the frontend (shared by the server and the tests)
+fn app_services(cfg: &mut web::ServiceConfig)
+{
+    cfg
+        //service the API sites/functions
+        .service(index)
+        .service(join_player)
+        .service(get_player_data)
+        .service(get_player_data_full)
+        .service(get_player_data_versioned)
+        .service(bet_money_get)
+        .service(bet_money_post)
+        .service(attack_player_get)
+        .service(attack_player_post)
+        .service(answer_question_get)
+        .service(answer_question_post)
+        .service(get_joker_fifty_fifty_get)
+        .service(get_joker_fifty_fifty_post)
+        .service(get_joker_audience_poll_get)
+        .service(get_joker_audience_poll_post)
+        .service(get_my_options)
+        .service(get_game_events)
+        .service(get_player_events)
+        .service(get_event)
+        .service(poll)
+        .service(replay)
+        .service(get_accuracy)
+        .service(get_summary)
+        .service(get_state)
+        .service(get_transitions)
+        .service(get_allowed_actions)
+        .service(get_correct_answer)
+        .service(get_admin_question)
+        .service(get_remaining_questions)
+        .service(get_current_questions)
+        .service(peek_next)
+        .service(give_money_post)
+        .service(set_money)
+        .service(set_jokers)
+        .service(set_jokers_all)
+        .service(set_player_disabled)
+        .service(kick_player_get)
+        .service(kick_player_post)
+        .service(announce)
+        .service(reveal_player)
+        .service(bonus_fastest)
+        .service(spin_bonus)
+        .service(fix_correct_answer)
+        .service(recompute_results)
+        .service(confirm_game_end)
+        .service(new_game)
+        .service(end_game)
+        .service(activate_next_question_get)
+        .service(reveal_question_post)
+        .service(force_question_answering_get)
+        .service(force_question_results_get)
+        .service(host_commands)
+        .service(set_next_question)
+        .service(shuffle_remaining)
+        .service(start_question)
+        .service(load_questions)
+        .service(load_questions_csv)
+        .service(load_questions_filtered)
+        .service(validate_questions_file)
+
+        //JSON 404 for all other API paths (must be after the API services, but before the static files)
+        .service(web::scope("/api").default_service(web::route().to(unknown_endpoint)))
+
+        //publish the gameshow's frontend (static files)
+        //(must be last, so that the specific handlers are served)
+        .service(actix_files::Files::new("/", "./Gameshow").index_file("play.htm"));
+        //.service(actix_files::Files::new("/", "./Gameshow").show_files_listing())
+}
+
+//read the questions at startup, but start without questions instead of failing (they can still be loaded with loadQuestions)
+async fn read_startup_questions(questions_file: &str) -> Vec<Question>
+{
+    match read_questions(questions_file).await
+    {
+        Ok(questions) => questions,
+        Err(err) => {
+            let reason = if err.kind() == std::io::ErrorKind::NotFound { "was not found" } else { "could not be parsed" };
+            eprintln!("Warning: questions file {} {} ({})! Starting without questions, set QUESTIONS_FILE or use loadQuestions.",
+                questions_file, reason, err);
+            Vec::new()
+        },
+    }
+}
+
+//background task saving the game state at most once per SAVE_INTERVAL_MS when it changed
+fn spawn_state_saver(data: web::Data<GameshowData>)
+{
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(Duration::from_millis(data.config.save_interval_ms));
+        let mut saved_roster_version = 0;
+        loop
+        {
+            interval.tick().await;
+            let roster_version = data.roster_version.load(Ordering::Relaxed);
+            if data.state_dirty.swap(false, Ordering::Relaxed) || roster_version != saved_roster_version
+            {
+                saved_roster_version = roster_version;
+                if let Err(err) = save_state(&data).await
+                {
+                    eprintln!("Warning: could not save the game state: {}", err);
+                }
+            }
+        }
+    });
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()>
+{
+    dotenv().ok();
+    let config = match Config::from_env()
+    {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("Error: invalid configuration: {}", err);
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, err));
+        },
+    };
+    println!("Configuration: {}", serde_json::to_string(&config).unwrap_or_default());
+
+    let questions = read_startup_questions(&config.questions_file).await;
+    let data = web::Data::new(GameshowData::new(config, questions));
+    match restore_state(&data).await
+    {
+        Ok(true) => println!("Restored the game state from STATE_FILE."),
+        Ok(false) => {},
+        Err(err) => eprintln!("Warning: the game state could not be restored from STATE_FILE ({})! Starting a new game.", err),
+    }
+    
+    spawn_state_ticker(data.clone());
+    spawn_state_saver(data.clone());
+
+    //the default number of workers is fine: every worker gets a clone of the same shared data (see GameshowData)
+    let server_data = data.clone();
+    let request_log = data.config.request_log;
+    let result = HttpServer::new(move || {
+        App::new()
+            //shared data to store the gameshow state etc.
+            .app_data(server_data.clone())
+
+            //structured request logging with the duration of every request
+            .wrap_fn(move |req, srv| {
+                let start = Instant::now();
+                let method = req.method().to_string();
+                let path = String::from(req.path());
+                let query = String::from(req.query_string());
+                let response = srv.call(req);
+                async move {
+                    let response = response.await?;
+                    if request_log
+                    {
+                        println!("{}", request_log_line(&method, &path, &query, response.status(), start.elapsed()));
+                    }
+                    Ok(response)
+                }
+            })
+
+            .configure(app_services)
+    })
+    .bind("127.0.0.1:8000")?
+    .run()
+    .await;
+    
+    //final save on shutdown
+    save_state(&data).await?;
+    result
+}
+
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use actix_web::dev::{Service, ServiceResponse};
+    use actix_web::http::StatusCode;
+    use actix_web::test;
+    use serde_json::json;
+    
+    //Config::from_env reads the ENV variables, which the configuration tests change
+    static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    
+    //the default configuration (without .env)
+    fn test_config() -> Config
+    {
+        let _lock = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        Config::from_env().expect("the default configuration is valid")
+    }
+    
+    fn test_data(config: Config, questions: Vec<Question>) -> web::Data<GameshowData>
+    {
+        web::Data::new(GameshowData::new(config, questions))
+    }
+    
+    fn test_question(question: serde_json::Value) -> Question
+    {
+        serde_json::from_value(question).expect("valid question")
+    }
+    
+    //normal question with four answers
+    fn normal_question(correct_answer: usize) -> Question
+    {
+        test_question(json!({"question_type": "NormalQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2", "A3", "A4"], "correct_answer": correct_answer}))
+    }
+    
+    //the app like in main, without the background tasks
+    async fn test_app(data: &web::Data<GameshowData>) -> impl Service<Request = actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>
+    {
+        test::init_service(App::new().app_data(data.clone()).configure(app_services)).await
+    }
+    
+    //call the API and return the status and the body as text
+    async fn call<S>(app: &mut S, request: actix_http::Request) -> (StatusCode, String)
+        where S: Service<Request = actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>
+    {
+        let response = test::call_service(app, request).await;
+        let status = response.status();
+        let body = test::read_body(response).await;
+        (status, String::from_utf8_lossy(&body).into_owned())
+    }
+    
+    async fn get<S>(app: &mut S, uri: &str) -> (StatusCode, String)
+        where S: Service<Request = actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>
+    {
+        call(app, test::TestRequest::get().uri(uri).to_request()).await
+    }
+    
+    async fn post<S>(app: &mut S, uri: &str, body: serde_json::Value) -> (StatusCode, String)
+        where S: Service<Request = actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>
+    {
+        call(app, test::TestRequest::post().uri(uri).set_json(&body).to_request()).await
+    }
+    
+    //post as the host with the X-Admin-Token header
+    async fn post_admin<S>(app: &mut S, uri: &str, body: serde_json::Value, token: &str) -> (StatusCode, String)
+        where S: Service<Request = actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>
+    {
+        call(app, test::TestRequest::post().uri(uri).header("X-Admin-Token", token).set_json(&body).to_request()).await
+    }
+    
+    async fn join<S>(app: &mut S, name: &str)
+        where S: Service<Request = actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>
+    {
+        let (status, body) = get(app, &format!("/api/joinPlayer?name={}", name)).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+    }
+    
+    #[actix_rt::test]
+    async fn set_jokers_all_adds_delta_to_every_player()
+    {
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        for name in ["Alice", "Bob", "Carol"].iter()
+        {
+            join(&mut app, name).await;
+        }
+        for (player, jokers) in data.player_data.write().await.iter_mut().zip([0, 1, 3].iter())
+        {
+            player.jokers = *jokers;
+        }
+        
+        let (status, _) = post(&mut app, "/api/setJokersAll", json!({"delta": 2})).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, body) = post_admin(&mut app, "/api/setJokersAll", json!({"delta": 2}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        let result: Vec<SetJokersData> = serde_json::from_str(&body).unwrap();
+        let jokers: Vec<usize> = result.iter().map(|player| player.jokers).collect();
+        assert_eq!(jokers, vec![2, 3, 5]);
+        
+        let (status, _) = post_admin(&mut app, "/api/setJokersAll", json!({"jokers": 1, "delta": 2}), "secret").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = post_admin(&mut app, "/api/setJokersAll", json!({"delta": -10}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(data.player_data.read().await.iter().all(|player| player.jokers == 0));
+    }
+    
+    //run the state transitions like the background ticker
+    async fn tick(data: &web::Data<GameshowData>)
+    {
+        check_state_add_events(data.clone()).await;
+    }
+    
+    async fn question_state(data: &web::Data<GameshowData>) -> QuestionState
+    {
+        *data.current_question_state.read().await
+    }
+    
+    async fn event_names(data: &web::Data<GameshowData>) -> Vec<String>
+    {
+        data.game_events.read().await.iter().map(|event| event.event_name.clone()).collect()
+    }
+    
+    //activate the next question and let the ticker begin it
+    async fn next_question<S>(app: &mut S, data: &web::Data<GameshowData>)
+        where S: Service<Request = actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>
+    {
+        let (status, body) = get(app, "/api/activateNextQuestion").await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        tick(data).await;
+    }
+    
+    async fn answer<S>(app: &mut S, name: &str, answer: usize) -> StatusCode
+        where S: Service<Request = actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>
+    {
+        get(app, &format!("/api/answerQuestion?name={}&answer={}", name, answer)).await.0
+    }
+    
+    #[actix_rt::test]
+    async fn seeded_fifty_fifty_is_reproducible()
+    {
+        let mut removed = Vec::new();
+        for _ in 0 .. 2
+        {
+            let mut config = test_config();
+            config.rng_seed = Some(42);
+            let data = test_data(config, vec![test_question(json!({"question_type": "NormalQuestion", "category": "Test",
+                "question": "Test question", "answers": ["A1", "A2", "A3", "A4", "A5", "A6"], "correct_answer": 3}))]);
+            let mut app = test_app(&data).await;
+            join(&mut app, "Alice").await;
+            next_question(&mut app, &data).await;
+            let (status, body) = get(&mut app, "/api/getJokerFiftyFifty?name=Alice").await;
+            assert_eq!(status, StatusCode::OK);
+            let wrong_answers: Vec<usize> = serde_json::from_str(&body).unwrap();
+            assert_eq!(wrong_answers.len(), 2);
+            assert!(!wrong_answers.contains(&3));
+            removed.push(wrong_answers);
+        }
+        assert_eq!(removed[0], removed[1]);
+    }
+    
+    #[actix_rt::test]
+    async fn explanation_is_only_shown_with_the_results()
+    {
+        let question = test_question(json!({"question_type": "NormalQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1, "explanation": "Because A1"}));
+        let data = test_data(test_config(), vec![question]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        
+        let events = data.game_events.read().await;
+        let begin = events.iter().find(|event| event.event_name == "BeginNormalQAnswering").unwrap();
+        assert!(!serde_json::to_string(begin).unwrap().contains("Because A1"));
+        match &events.iter().find(|event| event.event_name == "ShowResults").unwrap().event
+        {
+            EventType::ShowResults(results) => assert_eq!(results.explanation.as_deref(), Some("Because A1")),
+            _ => panic!("ShowResults expected"),
+        }
+    }
+    
+    //let the ticker advance a betting question until the answering begins
+    async fn begin_betting_answering(data: &web::Data<GameshowData>)
+    {
+        for _ in 0 .. 3
+        {
+            tick(data).await;
+        }
+        assert!(question_state(data).await == QuestionState::BettingQAnswering(false));
+    }
+    
+    #[actix_rt::test]
+    async fn oversized_bet_is_clamped_at_scoring()
+    {
+        let question = test_question(json!({"question_type": "BettingQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1}));
+        let data = test_data(test_config(), vec![question]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        let (status, _) = get(&mut app, "/api/betMoney?name=Alice&money_bet=300").await;
+        assert_eq!(status, StatusCode::OK);
+        begin_betting_answering(&data).await;
+        //the host lowers the money below the bet
+        data.player_data.write().await[0].money = 100;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        
+        assert!(question_state(&data).await == QuestionState::Results(false));
+        assert_eq!(data.player_data.read().await[0].money, 200);
+        let events = data.game_events.read().await;
+        match &events.iter().find(|event| event.event_name == "BetClamped").unwrap().event
+        {
+            EventType::BetClamped(clamped) => assert_eq!((clamped.name.as_str(), clamped.money_bet, clamped.money), ("Alice", 300, 100)),
+            _ => panic!("BetClamped expected"),
+        }
+    }
+    
+    #[actix_rt::test]
+    async fn answer_change_within_grace_counts()
+    {
+        let mut config = test_config();
+        config.answer_grace_secs = 60;
+        let data = test_data(config, vec![normal_question(2)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        //everyone answered, but the grace is not over yet
+        assert!(question_state(&data).await == QuestionState::NormalQAnswering(false));
+        assert_eq!(answer(&mut app, "Alice", 2).await, StatusCode::OK);
+        
+        *data.answers_ready_at.lock().await = Instant::now().checked_sub(Duration::from_secs(60));
+        tick(&data).await;
+        assert!(question_state(&data).await == QuestionState::Results(false));
+        assert_eq!(data.player_data.read().await[0].money, 1000);
+    }
+    
+    #[actix_rt::test]
+    async fn allowed_actions_follow_the_state()
+    {
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        let allowed_actions = |body: String| serde_json::from_str::<AllowedActionsData>(&body).unwrap().actions;
+        
+        next_question(&mut app, &data).await;
+        let (status, body) = get(&mut app, "/api/allowedActions").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(allowed_actions(body).contains(&String::from("answerQuestion")));
+        
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        let (_, body) = get(&mut app, "/api/allowedActions").await;
+        let actions = allowed_actions(body);
+        assert!(!actions.contains(&String::from("answerQuestion")));
+        assert!(actions.contains(&String::from("activateNextQuestion")));
+    }
+    
+    #[actix_rt::test]
+    async fn one_by_one_reveal_shows_each_player_in_turn()
+    {
+        let mut config = test_config();
+        config.reveal_mode = String::from("OneByOne");
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Bob", 2).await, StatusCode::OK);
+        tick(&data).await;
+        let answers = |body: String| serde_json::from_str::<Vec<PlayerData>>(&body).unwrap().iter().map(|player| player.answer).collect::<Vec<usize>>();
+        let (_, body) = get(&mut app, "/api/getPlayerData").await;
+        assert_eq!(answers(body), vec![0, 0]);
+        assert!(!event_names(&data).await.contains(&String::from("RoundSummary")));
+        
+        let (status, _) = call(&mut app, test::TestRequest::post().uri("/api/revealPlayer?name=Alice").to_request()).await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, body) = get_admin(&mut app, "/api/getPlayerDataFull", "secret").await;
+        assert_eq!(answers(body), vec![1, 0]);
+        assert!(!event_names(&data).await.contains(&String::from("RoundSummary")));
+        
+        let (status, _) = call(&mut app, test::TestRequest::post().uri("/api/revealPlayer?name=Bob").to_request()).await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, body) = get(&mut app, "/api/getPlayerData").await;
+        assert_eq!(answers(body), vec![1, 2]);
+        let events = data.game_events.read().await;
+        let reveals: Vec<(String, usize, bool)> = events.iter().filter_map(|event| match &event.event
+        {
+            EventType::RevealPlayer(reveal) => Some((reveal.name.clone(), reveal.answer, reveal.correct)),
+            _ => None,
+        }).collect();
+        assert_eq!(reveals, vec![(String::from("Alice"), 1, true), (String::from("Bob"), 2, false)]);
+        assert_eq!(events.last().unwrap().event_name, "RoundSummary");
+    }
+    
+    #[actix_rt::test]
+    async fn ticker_transitions_without_get_game_events()
+    {
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        spawn_state_ticker(data.clone());
+        let (status, _) = get(&mut app, "/api/activateNextQuestion").await;
+        assert_eq!(status, StatusCode::OK);
+        actix_web::rt::time::delay_for(Duration::from_millis(500)).await;
+        assert!(question_state(&data).await == QuestionState::NormalQAnswering(false));
+        
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        actix_web::rt::time::delay_for(Duration::from_millis(500)).await;
+        assert!(question_state(&data).await == QuestionState::Results(false));
+        assert_eq!(event_names(&data).await, vec!["PlayerJoined", "BeginNormalQAnswering", "ShowResults", "RoundSummary"]);
+    }
+    
+    #[actix_rt::test]
+    async fn join_reports_reconnects()
+    {
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        let (status, body) = get(&mut app, "/api/joinPlayer?name=Alice").await;
+        assert_eq!(status, StatusCode::OK);
+        let joined: JoinPlayerResult = serde_json::from_str(&body).unwrap();
+        assert!(!joined.reconnected);
+        assert_eq!((joined.name.as_str(), joined.money), ("Alice", 500));
+        
+        let (status, body) = get(&mut app, "/api/joinPlayer?name=Alice").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(serde_json::from_str::<JoinPlayerResult>(&body).unwrap().reconnected);
+        assert_eq!(data.player_data.read().await.len(), 1);
+    }
+    
+    #[actix_rt::test]
+    async fn negative_give_money_stops_at_the_floor()
+    {
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        let (status, body) = post(&mut app, "/api/giveMoney", json!({"name": "Alice", "money": -1000})).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(serde_json::from_str::<GiveMoneyData>(&body).unwrap().money, MONEY_FLOOR);
+        
+        let mut config = test_config();
+        config.strict_give_money = true;
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        let (status, _) = post(&mut app, "/api/giveMoney", json!({"name": "Alice", "money": -1000})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(data.player_data.read().await[0].money, 500);
+    }
+    
+    #[actix_rt::test]
+    async fn host_command_channel_forces_the_results()
+    {
+        use futures::SinkExt;
+        
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        let (status, _) = get(&mut app, "/api/ws?token=wrong").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        
+        let server_data = data.clone();
+        let mut server = test::start(move || App::new().app_data(server_data.clone()).configure(app_services));
+        let mut connection = server.ws_at("/api/ws?token=secret").await.unwrap();
+        connection.send(ws::Message::Text(String::from(r#"{"id": 7, "command": "forceQuestionResults"}"#))).await.unwrap();
+        let ack: HostCommandAck = match connection.next().await.unwrap().unwrap()
+        {
+            ws::Frame::Text(text) => serde_json::from_slice(&text).unwrap(),
+            _ => panic!("text frame expected"),
+        };
+        assert_eq!((ack.id, ack.command.as_str(), ack.ok, ack.status), (Some(7), "forceQuestionResults", true, 200));
+        assert!(question_state(&data).await == QuestionState::NormalQAnswering(true));
+        tick(&data).await;
+        assert!(question_state(&data).await == QuestionState::Results(false));
+        
+        //the same command is rejected like the REST endpoint in the wrong state
+        connection.send(ws::Message::Text(String::from(r#"{"command": "forceQuestionResults"}"#))).await.unwrap();
+        let ack: HostCommandAck = match connection.next().await.unwrap().unwrap()
+        {
+            ws::Frame::Text(text) => serde_json::from_slice(&text).unwrap(),
+            _ => panic!("text frame expected"),
+        };
+        assert_eq!((ack.ok, ack.status), (false, 406));
+        //other host actions are not available as commands, the ack lists the available ones
+        connection.send(ws::Message::Text(String::from(r#"{"command": "endGame"}"#))).await.unwrap();
+        let ack: HostCommandAck = match connection.next().await.unwrap().unwrap()
+        {
+            ws::Frame::Text(text) => serde_json::from_slice(&text).unwrap(),
+            _ => panic!("text frame expected"),
+        };
+        assert_eq!((ack.ok, ack.status), (false, 404));
+        assert!(HOST_COMMANDS.iter().all(|command| ack.body.contains(command)));
+        connection.send(ws::Message::Close(None)).await.unwrap();
+        assert!(matches!(connection.next().await, Some(Ok(ws::Frame::Close(_)))));
+        server.stop().await;
+    }
+    
+    #[actix_rt::test]
+    async fn auto_start_begins_with_enough_players()
+    {
+        let mut config = test_config();
+        config.auto_start = true;
+        config.min_players = 2;
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        tick(&data).await;
+        assert!(question_state(&data).await == QuestionState::Results(false));
+        
+        join(&mut app, "Bob").await;
+        tick(&data).await;
+        assert!(question_state(&data).await == QuestionState::NormalQAnswering(false));
+        assert_eq!(data.current_question.load(Ordering::Relaxed), 1);
+    }
+    
+    #[actix_rt::test]
+    async fn bets_above_max_bet_are_rejected()
+    {
+        let question = test_question(json!({"question_type": "BettingQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1, "max_bet": 200}));
+        let data = test_data(test_config(), vec![question]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        let (status, _) = get(&mut app, "/api/betMoney?name=Alice&money_bet=300").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = get(&mut app, "/api/betMoney?name=Alice&money_bet=150").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(data.player_data.read().await[0].money_bet, 150);
+    }
+    
+    #[actix_rt::test]
+    async fn load_questions_emits_questions_loaded()
+    {
+        let data = test_data(test_config(), Vec::new());
+        let mut app = test_app(&data).await;
+        let (status, body) = post(&mut app, "/api/loadQuestions", json!({"filename": "questions-example.json"})).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        let count = read_questions("./Questions/questions-example.json").await.unwrap().len();
+        assert_eq!(body, count.to_string());
+        let events = data.game_events.read().await;
+        match &events.last().unwrap().event
+        {
+            EventType::QuestionsLoaded(loaded) => assert_eq!(loaded.count, count),
+            _ => panic!("QuestionsLoaded expected"),
+        }
+    }
+    
+    #[actix_rt::test]
+    async fn multi_select_scores_partial_credit()
+    {
+        let question = test_question(json!({"question_type": "MultiSelectQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2", "A3", "A4"], "correct_answer": 0, "correct_answers": [1, 2]}));
+        let data = test_data(test_config(), vec![question]);
+        let mut app = test_app(&data).await;
+        for name in ["Exact", "Partial", "Over"].iter()
+        {
+            join(&mut app, name).await;
+        }
+        next_question(&mut app, &data).await;
+        assert!(event_names(&data).await.contains(&String::from("BeginMultiSelectQAnswering")));
+        let (status, _) = get(&mut app, "/api/answerQuestion?name=Exact&answers=2,1").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = post(&mut app, "/api/answerQuestion", json!({"name": "Partial", "answers": [1]})).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = get(&mut app, "/api/answerQuestion?name=Over&answers=1,2,3,4").await;
+        assert_eq!(status, StatusCode::OK);
+        tick(&data).await;
+        
+        let money: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money).collect();
+        //exact: full reward, partial: half of it, over-selection: two correct minus two wrong picks
+        assert_eq!(money, vec![500 + 500, 500 + 250, 500]);
+    }
+    
+    #[actix_rt::test]
+    async fn correct_answer_is_only_given_after_scoring()
+    {
+        let data = test_data(test_config(), vec![normal_question(3)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        let (status, _) = get(&mut app, "/api/correctAnswer").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        let (status, body) = get(&mut app, "/api/correctAnswer").await;
+        assert_eq!(status, StatusCode::OK);
+        let correct: CorrectAnswerData = serde_json::from_str(&body).unwrap();
+        assert_eq!(correct.question_number, 1);
+        assert_eq!(correct.correct_answer, 3);
+        assert_eq!(correct.answer.as_deref(), Some("A3"));
+    }
+    
+    #[actix_rt::test]
+    async fn player_data_is_not_modified_for_the_current_version()
+    {
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        let (status, body) = get(&mut app, "/api/playerData").await;
+        assert_eq!(status, StatusCode::OK);
+        let version = serde_json::from_str::<PlayerDataVersioned>(&body).unwrap().version;
+        
+        join(&mut app, "Alice").await;
+        let (status, body) = get(&mut app, &format!("/api/playerData?version={}", version)).await;
+        assert_eq!(status, StatusCode::OK);
+        let roster: PlayerDataVersioned = serde_json::from_str(&body).unwrap();
+        assert!(roster.version > version);
+        assert_eq!(roster.players.len(), 1);
+        let (status, body) = get(&mut app, &format!("/api/playerData?version={}", roster.version)).await;
+        assert_eq!(status, StatusCode::NOT_MODIFIED);
+        assert!(body.is_empty());
+        
+        let (status, _) = get(&mut app, "/api/kickPlayer?name=Alice").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, body) = get(&mut app, &format!("/api/playerData?version={}", roster.version)).await;
+        assert_eq!(status, StatusCode::OK);
+        let kicked: PlayerDataVersioned = serde_json::from_str(&body).unwrap();
+        assert!(kicked.version > roster.version);
+        assert!(kicked.players.is_empty());
+    }
+    
+    #[actix_rt::test]
+    async fn decimal_estimation_rewards_the_closer_guess()
+    {
+        let question = test_question(json!({"question_type": "EstimationQuestion", "category": "Test", "question": "Test question",
+            "answers": [], "correct_answer": 3, "correct_answer_f": 3.25}));
+        let data = test_data(test_config(), vec![question]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        next_question(&mut app, &data).await;
+        let (status, _) = get(&mut app, "/api/answerQuestion?name=Alice&estimate_f=3.0").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = get(&mut app, "/api/answerQuestion?name=Bob&estimate_f=3.2").await;
+        assert_eq!(status, StatusCode::OK);
+        tick(&data).await;
+        
+        let money: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money).collect();
+        assert_eq!(money, vec![500, 500 + 1000]);
+    }
+    
+    #[actix_rt::test]
+    async fn comeback_multiplier_boosts_only_the_trailing_player()
+    {
+        let mut config = test_config();
+        config.comeback_multiplier = 2.0;
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Leader").await;
+        join(&mut app, "Trailing").await;
+        data.player_data.write().await[0].money = 2000;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Leader", 1).await, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Trailing", 1).await, StatusCode::OK);
+        tick(&data).await;
+        
+        let money: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money).collect();
+        assert_eq!(money, vec![2000 + 500, 500 + 1000]);
+    }
+    
+    #[actix_rt::test]
+    async fn state_reports_ready_before_the_transition()
+    {
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        let (_, body) = get(&mut app, "/api/state").await;
+        let state: StateData = serde_json::from_str(&body).unwrap();
+        assert_eq!(state.state, "NormalQAnswering");
+        assert!(!state.ready);
+        
+        assert_eq!(answer(&mut app, "Bob", 2).await, StatusCode::OK);
+        let (_, body) = get(&mut app, "/api/state").await;
+        let state: StateData = serde_json::from_str(&body).unwrap();
+        assert_eq!(state.state, "NormalQAnswering");
+        assert!(state.ready);
+        let (_, body) = get(&mut app, "/api/allowedActions").await;
+        let actions: AllowedActionsData = serde_json::from_str(&body).unwrap();
+        assert!(actions.ready);
+        
+        tick(&data).await;
+        let (_, body) = get(&mut app, "/api/state").await;
+        let state: StateData = serde_json::from_str(&body).unwrap();
+        assert_eq!(state.state, "Results");
+        assert!(!state.ready);
+    }
+    
+    #[actix_rt::test]
+    async fn category_multiplier_doubles_the_reward()
+    {
+        let mut config = test_config();
+        config.category_multipliers.insert(String::from("Science"), 2.0);
+        let science = test_question(json!({"question_type": "NormalQuestion", "category": "Science", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1}));
+        let data = test_data(config, vec![normal_question(1), science]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        assert_eq!(data.player_data.read().await[0].money, 500 + 500);
+        
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        assert_eq!(data.player_data.read().await[0].money, 500 + 500 + 1000);
+    }
+    
+    #[actix_rt::test]
+    async fn unknown_api_paths_get_a_json_404()
+    {
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        let (status, body) = get(&mut app, "/api/foo").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let unknown: UnknownEndpointData = serde_json::from_str(&body).unwrap();
+        assert_eq!(unknown.error, "UnknownEndpoint");
+        assert_eq!(unknown.path, "/api/foo");
+        
+        let (status, body) = get(&mut app, "/play.htm").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("<html") || body.contains("<!DOCTYPE"));
+    }
+    
+    #[actix_rt::test]
+    async fn answer_lock_rejects_a_second_answer()
+    {
+        for &answer_lock in [false, true].iter()
+        {
+            let mut config = test_config();
+            config.answer_lock = answer_lock;
+            let data = test_data(config, vec![normal_question(1)]);
+            let mut app = test_app(&data).await;
+            join(&mut app, "Alice").await;
+            join(&mut app, "Bob").await;
+            next_question(&mut app, &data).await;
+            assert_eq!(answer(&mut app, "Alice", 2).await, StatusCode::OK);
+            let expected = if answer_lock { StatusCode::CONFLICT } else { StatusCode::OK };
+            assert_eq!(answer(&mut app, "Alice", 1).await, expected);
+            let expected_answer = if answer_lock { 2 } else { 1 };
+            assert_eq!(data.player_data.read().await[0].answer, expected_answer);
+        }
+    }
+    
+    #[actix_rt::test]
+    async fn betting_loss_to_the_floor_emits_player_on_floor_once()
+    {
+        let question = test_question(json!({"question_type": "BettingQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1}));
+        let data = test_data(test_config(), vec![question]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        next_question(&mut app, &data).await;
+        let (status, _) = get(&mut app, "/api/betMoney?name=Alice&money_bet=500").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = get(&mut app, "/api/betMoney?name=Bob&money_bet=100").await;
+        assert_eq!(status, StatusCode::OK);
+        begin_betting_answering(&data).await;
+        assert_eq!(answer(&mut app, "Alice", 2).await, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Bob", 2).await, StatusCode::OK);
+        tick(&data).await;
+        
+        let money: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money).collect();
+        assert_eq!(money, vec![MONEY_FLOOR, 400]);
+        let events = data.game_events.read().await;
+        let floored: Vec<&str> = events.iter().filter_map(|event| match &event.event
+        {
+            EventType::PlayerOnFloor(floor) => Some(floor.name.as_str()),
+            _ => None,
+        }).collect();
+        assert_eq!(floored, vec!["Alice"]);
+    }
+    
+    #[actix_rt::test]
+    async fn game_events_are_filtered_by_type()
+    {
+        let data = test_data(test_config(), vec![normal_question(1), normal_question(2)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        next_question(&mut app, &data).await;
+        
+        let (status, body) = get(&mut app, "/api/getGameEvents?type=BeginNormalQAnswering").await;
+        assert_eq!(status, StatusCode::OK);
+        let events: Vec<Event> = serde_json::from_str(&body).unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.event_name == "BeginNormalQAnswering"));
+        let (_, body) = get(&mut app, &format!("/api/getGameEvents?type=BeginNormalQAnswering&since={}", events[0].id)).await;
+        let since: Vec<Event> = serde_json::from_str(&body).unwrap();
+        assert_eq!(since.len(), 1);
+        assert_eq!(since[0].id, events[1].id);
+        let (status, body) = get(&mut app, "/api/getGameEvents?type=NoSuchEvent").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "[]");
+    }
+    
+    #[actix_rt::test]
+    async fn state_saves_are_throttled()
+    {
+        let state_file = std::env::temp_dir().join(format!("gameshow-state-test-{}.json", std::process::id()));
+        let _ = fs::remove_file(&state_file);
+        let mut config = test_config();
+        config.save_interval_ms = 100;
+        config.state_file = Some(state_file.to_string_lossy().into_owned());
+        let data = test_data(config, vec![normal_question(1); 8]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        spawn_state_saver(data.clone());
+        
+        //16 transitions within about 300ms, each file that shows up again is one write
+        let start = Instant::now();
+        let mut writes = 0;
+        for _ in 0 .. 8
+        {
+            next_question(&mut app, &data).await;
+            assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+            tick(&data).await;
+            actix_web::rt::time::delay_for(Duration::from_millis(40)).await;
+            if fs::remove_file(&state_file).is_ok()
+            {
+                writes += 1;
+            }
+        }
+        //at most one write per SAVE_INTERVAL_MS (and the immediate first one)
+        let max_writes = start.elapsed().as_millis() / 100 + 1;
+        assert!(writes >= 1 && writes <= max_writes && writes < 16, "{} writes", writes);
+        
+        //a change after the last save is still saved
+        let (status, _) = post(&mut app, "/api/giveMoney", json!({"name": "Alice", "money": 1234})).await;
+        assert_eq!(status, StatusCode::OK);
+        actix_web::rt::time::delay_for(Duration::from_millis(250)).await;
+        let saved: SavedState = serde_json::from_str(&fs::read_to_string(&state_file).unwrap()).unwrap();
+        let _ = fs::remove_file(&state_file);
+        assert_eq!(saved.current_question, 8);
+        assert!(saved.question_state == QuestionState::Results(false));
+        assert_eq!(saved.player_data[0].money, 500 + 8 * 500 + 1234);
+    }
+    
+    #[actix_rt::test]
+    async fn saved_state_is_restored_at_startup()
+    {
+        let state_file = std::env::temp_dir().join(format!("gameshow-restore-test-{}.json", std::process::id()));
+        let state_config = || {
+            let mut config = test_config();
+            config.state_file = Some(state_file.to_string_lossy().into_owned());
+            config
+        };
+        let data = test_data(state_config(), vec![normal_question(1); 2]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        save_state(&data).await.unwrap();
+        
+        let restored = test_data(state_config(), vec![normal_question(1); 2]);
+        assert!(restore_state(&restored).await.unwrap());
+        assert!(question_state(&restored).await == QuestionState::Results(false));
+        assert_eq!(restored.current_question.load(Ordering::Relaxed), 1);
+        assert_eq!(restored.player_data.read().await[0].money, 1000);
+        assert_eq!(restored.player_index.read().await.get("Alice"), Some(&0));
+        assert_eq!(event_names(&restored).await, event_names(&data).await);
+        
+        //the saved question must be in the loaded questions
+        let without_questions = test_data(state_config(), Vec::new());
+        assert!(restore_state(&without_questions).await.is_err());
+        let _ = fs::remove_file(&state_file);
+        assert!(without_questions.player_data.read().await.is_empty());
+    }
+    
+    #[actix_rt::test]
+    async fn set_money_sets_the_exact_balance()
+    {
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        for &previous in [0, 3000].iter()
+        {
+            let (status, _) = post(&mut app, "/api/giveMoney", json!({"name": "Alice", "money": previous})).await;
+            assert_eq!(status, StatusCode::OK);
+            let (status, body) = post_admin(&mut app, "/api/setMoney", json!({"name": "Alice", "money": 1000}), "secret").await;
+            assert_eq!(status, StatusCode::OK);
+            let result: SetMoneyData = serde_json::from_str(&body).unwrap();
+            assert_eq!(result.name, "Alice");
+            assert_eq!(result.money, 1000);
+        }
+        let (_, body) = post_admin(&mut app, "/api/setMoney", json!({"name": "Alice", "money": -50}), "secret").await;
+        assert_eq!(serde_json::from_str::<SetMoneyData>(&body).unwrap().money, MONEY_FLOOR);
+        let (status, _) = post_admin(&mut app, "/api/setMoney", json!({"name": "Nobody", "money": 1000}), "secret").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        
+        //only the host may set the money
+        let (status, _) = post(&mut app, "/api/setMoney", json!({"name": "Alice", "money": 5000})).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = post_admin(&mut app, "/api/setMoney", json!({"name": "Alice", "money": 5000}), "wrong").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(data.player_data.read().await[0].money, MONEY_FLOOR);
+    }
+    
+    #[actix_rt::test]
+    async fn accepted_answers_are_rewarded()
+    {
+        let question = test_question(json!({"question_type": "NormalQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2", "A3"], "correct_answer": 1, "accepted_answers": [2]}));
+        let data = test_data(test_config(), vec![question]);
+        let mut app = test_app(&data).await;
+        for name in ["Alice", "Bob", "Carol"].iter()
+        {
+            join(&mut app, name).await;
+        }
+        next_question(&mut app, &data).await;
+        for (name, given) in ["Alice", "Bob", "Carol"].iter().zip([1, 2, 3].iter())
+        {
+            assert_eq!(answer(&mut app, name, *given).await, StatusCode::OK);
+        }
+        tick(&data).await;
+        
+        let money: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money).collect();
+        assert_eq!(money, vec![1000, 1000, 500]);
+        let out_of_range = test_question(json!({"question_type": "NormalQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1, "accepted_answers": [3]}));
+        assert_eq!(validate_questions(&[out_of_range]), Err(String::from("Question 1: accepted_answers must be 1 - len(answers)!")));
+    }
+    
+    #[actix_rt::test]
+    async fn request_log_line_has_the_status_without_names()
+    {
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        let (status, _) = get(&mut app, "/api/getPlayerData?name=Alice").await;
+        
+        let line = request_log_line("GET", "/api/getPlayerData", "name=Alice&since=3", status, Duration::from_micros(1500));
+        assert_eq!(line, "method=GET path=/api/getPlayerData query=\"name=***&since=3\" status=200 duration_ms=1.500");
+    }
+    
+    #[actix_rt::test]
+    async fn bonus_fastest_goes_to_the_earlier_correct_answer()
+    {
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        for name in ["Wrong", "Early", "Late"].iter()
+        {
+            join(&mut app, name).await;
+        }
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Wrong", 2).await, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Early", 1).await, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Late", 1).await, StatusCode::OK);
+        tick(&data).await;
+        
+        let (status, body) = post_admin(&mut app, "/api/bonusFastest", json!({}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        let bonus: BonusFastestData = serde_json::from_str(&body).unwrap();
+        assert_eq!(bonus.name, "Early");
+        assert_eq!(bonus.money, 500 + 500 + BONUS_FASTEST_MONEY);
+        let (status, _) = post_admin(&mut app, "/api/bonusFastest", json!({}), "secret").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(data.player_data.read().await[2].money, 1000);
+    }
+    
+    #[actix_rt::test]
+    async fn transition_unblocks_a_pending_poll()
+    {
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        let mut host = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        let uri = data.game_events.read().await.last().map_or(String::from("/api/poll"), |event| format!("/api/poll?since={}", event.id));
+        let pending = get(&mut app, &uri);
+        let transition = async {
+            actix_web::rt::time::delay_for(Duration::from_millis(150)).await;
+            next_question(&mut host, &data).await;
+        };
+        let ((status, body), _) = futures::join!(pending, transition);
+        assert_eq!(status, StatusCode::OK);
+        let result: PollResult = serde_json::from_str(&body).unwrap();
+        assert_eq!(result.events.len(), 1);
+        assert_eq!(result.events[0].event_name, "BeginNormalQAnswering");
+        assert_eq!(result.players.len(), 1);
+    }
+    
+    #[actix_rt::test]
+    async fn missing_questions_file_starts_without_questions()
+    {
+        let questions = read_startup_questions("./Questions/does-not-exist.json").await;
+        assert!(questions.is_empty());
+        let data = test_data(test_config(), questions);
+        let mut app = test_app(&data).await;
+        let (status, body) = get(&mut app, "/api/state").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(serde_json::from_str::<StateData>(&body).unwrap().num_questions, 0);
+        join(&mut app, "Alice").await;
+        let (status, body) = get(&mut app, "/api/activateNextQuestion").await;
+        assert_eq!(status, StatusCode::NOT_ACCEPTABLE);
+        assert_eq!(body, "No questions loaded!");
+    }
+    
+    #[actix_rt::test]
+    async fn player_is_only_reclaimed_with_the_right_pin()
+    {
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        let (status, body) = get(&mut app, "/api/joinPlayer?name=Alice&pin=1234").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(!serde_json::from_str::<JoinPlayerResult>(&body).unwrap().reconnected);
+        
+        let (status, body) = get(&mut app, "/api/joinPlayer?name=Alice&pin=1234").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(serde_json::from_str::<JoinPlayerResult>(&body).unwrap().reconnected);
+        let (status, _) = get(&mut app, "/api/joinPlayer?name=Alice&pin=4321").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _) = get(&mut app, "/api/joinPlayer?name=Alice").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(data.player_data.read().await.len(), 1);
+    }
+    
+    #[actix_rt::test]
+    async fn end_game_mid_answering_ends_the_game()
+    {
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(1), normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        
+        let (status, _) = post_admin(&mut app, "/api/endGame", json!({}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(question_state(&data).await == QuestionState::GameEnding);
+        let names = event_names(&data).await;
+        assert_eq!(names.last().map(String::as_str), Some("GameEnding"));
+        //the given answer was scored first (END_GAME_SCORING)
+        assert!(names.contains(&String::from("ShowResults")));
+        assert_eq!(data.player_data.read().await[0].money, 1000);
+        let (status, _) = post_admin(&mut app, "/api/endGame", json!({}), "secret").await;
+        assert_eq!(status, StatusCode::NOT_ACCEPTABLE);
+    }
+    
+    #[actix_rt::test]
+    async fn mutual_attack_does_not_depend_on_the_order()
+    {
+        let mut results = Vec::new();
+        for players in [["Alice", "Bob"], ["Bob", "Alice"]].iter()
+        {
+            let question = test_question(json!({"question_type": "VersusQuestion", "category": "Test", "question": "Test question",
+                "answers": ["A1", "A2"], "correct_answer": 1}));
+            let data = test_data(test_config(), vec![question]);
+            let mut app = test_app(&data).await;
+            for name in players.iter()
+            {
+                join(&mut app, name).await;
+            }
+            next_question(&mut app, &data).await;
+            let (status, _) = get(&mut app, "/api/attackPlayer?name=Alice&vs_player=Bob").await;
+            assert_eq!(status, StatusCode::OK);
+            let (status, _) = get(&mut app, "/api/attackPlayer?name=Bob&vs_player=Alice").await;
+            assert_eq!(status, StatusCode::OK);
+            tick(&data).await;
+            assert!(question_state(&data).await == QuestionState::VersusQAnswering(false));
+            assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+            assert_eq!(answer(&mut app, "Bob", 2).await, StatusCode::OK);
+            tick(&data).await;
+            
+            let mut money: Vec<(String, i64)> = data.player_data.read().await.iter().map(|player| (player.name.clone(), player.money)).collect();
+            money.sort();
+            results.push(money);
+        }
+        //Alice's correct attack halves Bob's money, Bob's wrong attack doubles Alice's money
+        assert_eq!(results[0], vec![(String::from("Alice"), 1000), (String::from("Bob"), 250)]);
+        assert_eq!(results[0], results[1]);
+    }
+    
+    #[actix_rt::test]
+    async fn bet_payout_ratio_multiplies_the_win()
+    {
+        let question = test_question(json!({"question_type": "BettingQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1, "bet_payout_ratio": 2.0}));
+        let data = test_data(test_config(), vec![question]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        next_question(&mut app, &data).await;
+        for name in ["Alice", "Bob"].iter()
+        {
+            let (status, _) = get(&mut app, &format!("/api/betMoney?name={}&money_bet=100", name)).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+        begin_betting_answering(&data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Bob", 2).await, StatusCode::OK);
+        tick(&data).await;
+        
+        let money: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money).collect();
+        //a wrong bet still only loses the bet
+        assert_eq!(money, vec![500 + 200, 500 - 100]);
+    }
+    
+    #[actix_rt::test]
+    async fn double_activation_advances_only_once()
+    {
+        let data = test_data(test_config(), vec![normal_question(1), normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        let (status, _) = get(&mut app, "/api/activateNextQuestion").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = get(&mut app, "/api/activateNextQuestion").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        tick(&data).await;
+        tick(&data).await;
+        
+        assert_eq!(data.current_question.load(Ordering::Relaxed), 1);
+        let begins = event_names(&data).await.iter().filter(|name| *name == "BeginNormalQAnswering").count();
+        assert_eq!(begins, 1);
+    }
+    
+    #[actix_rt::test]
+    async fn category_teaser_precedes_the_question()
+    {
+        let mut config = test_config();
+        config.category_teaser = true;
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        assert!(question_state(&data).await == QuestionState::CategoryTeaser(false));
+        assert_eq!(event_names(&data).await.last().map(String::as_str), Some("CategoryTeaser"));
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::NOT_ACCEPTABLE);
+        
+        let (status, _) = post(&mut app, "/api/revealQuestion", json!({})).await;
+        assert_eq!(status, StatusCode::OK);
+        tick(&data).await;
+        let names = event_names(&data).await;
+        assert_eq!(names[names.len() - 2 ..].to_vec(), vec![String::from("CategoryTeaser"), String::from("BeginNormalQAnswering")]);
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+    }
+    
+    #[actix_rt::test]
+    async fn attack_on_two_targets_splits_the_effect()
+    {
+        let mut config = test_config();
+        config.versus_max_targets = 2;
+        let question = test_question(json!({"question_type": "VersusQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1}));
+        let data = test_data(config, vec![question]);
+        let mut app = test_app(&data).await;
+        for name in ["Alice", "Bob", "Carol"].iter()
+        {
+            join(&mut app, name).await;
+        }
+        next_question(&mut app, &data).await;
+        let (status, _) = post(&mut app, "/api/attackPlayer", json!({"name": "Bob", "vs_players": ["Alice", "Bob"]})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = post(&mut app, "/api/attackPlayer", json!({"name": "Bob", "vs_players": ["Alice", "Nobody"]})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = post(&mut app, "/api/attackPlayer", json!({"name": "Alice", "vs_players": ["Bob", "Carol"]})).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = get(&mut app, "/api/attackPlayer?name=Bob&vs_player=Alice").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = get(&mut app, "/api/attackPlayer?name=Carol&vs_player=Alice").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(data.player_data.read().await[0].vs_players, vec![String::from("Bob"), String::from("Carol")]);
+        tick(&data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        for name in ["Bob", "Carol"].iter()
+        {
+            assert_eq!(answer(&mut app, name, 1).await, StatusCode::OK);
+        }
+        tick(&data).await;
+        
+        let money: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money).collect();
+        //Alice loses half twice from Bob and Carol, Bob and Carol lose a quarter each from Alice
+        assert_eq!(money, vec![125, 375, 375]);
+    }
+    
+    #[actix_rt::test]
+    async fn accuracy_counts_the_correct_answers()
+    {
+        let data = test_data(test_config(), vec![normal_question(1), normal_question(2)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        for (alice, bob) in [(1, 1), (1, 2)].iter()
+        {
+            next_question(&mut app, &data).await;
+            assert_eq!(answer(&mut app, "Alice", *alice).await, StatusCode::OK);
+            assert_eq!(answer(&mut app, "Bob", *bob).await, StatusCode::OK);
+            tick(&data).await;
+        }
+        
+        let (status, body) = get(&mut app, "/api/accuracy").await;
+        assert_eq!(status, StatusCode::OK);
+        let accuracy: Vec<AccuracyData> = serde_json::from_str(&body).unwrap();
+        let counts: Vec<(&str, usize, usize)> = accuracy.iter()
+            .map(|player| (player.name.as_str(), player.correct_count, player.answered_count)).collect();
+        assert_eq!(counts, vec![("Bob", 2, 2), ("Alice", 1, 2)]);
+    }
+    
+    #[actix_rt::test]
+    async fn estimation_without_players_shows_empty_results()
+    {
+        let question = test_question(json!({"question_type": "EstimationQuestion", "category": "Test", "question": "Test question",
+            "answers": [], "correct_answer": 42}));
+        let data = test_data(test_config(), vec![question]);
+        let mut app = test_app(&data).await;
+        next_question(&mut app, &data).await;
+        assert!(question_state(&data).await == QuestionState::EstimationQAnswering(false));
+        let (status, _) = get(&mut app, "/api/forceQuestionResults").await;
+        assert_eq!(status, StatusCode::OK);
+        tick(&data).await;
+        
+        assert!(question_state(&data).await == QuestionState::Results(false));
+        let events = data.game_events.read().await;
+        match &events.iter().find(|event| event.event_name == "ShowResults").unwrap().event
+        {
+            EventType::ShowResults(results) => assert!(results.player_data.is_empty()),
+            _ => panic!("ShowResults expected"),
+        }
+    }
+    
+    #[actix_rt::test]
+    async fn question_limit_ends_the_game_early()
+    {
+        let mut config = test_config();
+        config.question_limit = 3;
+        let data = test_data(config, vec![normal_question(1); 20]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        for _ in 0 .. 3
+        {
+            next_question(&mut app, &data).await;
+            assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+            tick(&data).await;
+        }
+        assert_eq!(data.current_question.load(Ordering::Relaxed), 3);
+        
+        next_question(&mut app, &data).await;
+        assert!(question_state(&data).await == QuestionState::GameEnding);
+        assert_eq!(event_names(&data).await.last().map(String::as_str), Some("GameEnding"));
+    }
+    
+    #[actix_rt::test]
+    async fn event_is_found_by_id()
+    {
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        let id = data.game_events.read().await.last().unwrap().id;
+        
+        let (status, body) = get(&mut app, &format!("/api/event/{}", id)).await;
+        assert_eq!(status, StatusCode::OK);
+        let event: Event = serde_json::from_str(&body).unwrap();
+        assert_eq!(event.id, id);
+        match event.event
+        {
+            EventType::PlayerJoined(joined) => assert_eq!(joined.name, "Bob"),
+            _ => panic!("PlayerJoined expected"),
+        }
+        let (status, _) = get(&mut app, &format!("/api/event/{}", id + 1)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+    
+    #[actix_rt::test]
+    async fn large_roster_keeps_the_index_in_sync()
+    {
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        for i in 0 .. 500
+        {
+            join(&mut app, &format!("Player{}", i)).await;
+        }
+        for i in (0 .. 500).step_by(3)
+        {
+            let (status, _) = get(&mut app, &format!("/api/kickPlayer?name=Player{}", i)).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+        {
+            let player_data = data.player_data.read().await;
+            let player_index = data.player_index.read().await;
+            assert_eq!(player_data.len(), 333);
+            assert_eq!(player_index.len(), 333);
+            for (i, player) in player_data.iter().enumerate()
+            {
+                assert_eq!(player_index.get(&player.name), Some(&i));
+            }
+        }
+        
+        //the readiness scan only passes once the last remaining player answered
+        next_question(&mut app, &data).await;
+        let names: Vec<String> = data.player_data.read().await.iter().map(|player| player.name.clone()).collect();
+        for name in names.iter()
+        {
+            assert!(question_state(&data).await == QuestionState::NormalQAnswering(false));
+            assert_eq!(answer(&mut app, name, 1).await, StatusCode::OK);
+        }
+        assert!(question_state(&data).await == QuestionState::NormalQAnswering(true));
+        assert_eq!(answer(&mut app, "Player0", 1).await, StatusCode::NOT_ACCEPTABLE);
+    }
+    
+    #[actix_rt::test]
+    async fn versus_selections_are_hidden_until_the_results()
+    {
+        let question = test_question(json!({"question_type": "VersusQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1}));
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![question]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        next_question(&mut app, &data).await;
+        let (status, _) = get(&mut app, "/api/attackPlayer?name=Alice&vs_player=Bob").await;
+        assert_eq!(status, StatusCode::OK);
+        
+        let (_, body) = get(&mut app, "/api/getPlayerData").await;
+        let public: Vec<PlayerData> = serde_json::from_str(&body).unwrap();
+        assert_eq!(public[0].vs_player, "");
+        assert!(public[0].vs_players.is_empty());
+        //the host sees the selections live
+        let (_, body) = get_admin(&mut app, "/api/getPlayerDataFull", "secret").await;
+        let full: Vec<PlayerData> = serde_json::from_str(&body).unwrap();
+        assert_eq!(full[0].vs_player, "Bob");
+        
+        let (status, _) = get(&mut app, "/api/attackPlayer?name=Bob&vs_player=Alice").await;
+        assert_eq!(status, StatusCode::OK);
+        tick(&data).await;
+        let (_, body) = get(&mut app, "/api/getPlayerData").await;
+        let public: Vec<PlayerData> = serde_json::from_str(&body).unwrap();
+        assert_eq!(public[0].vs_player, "");
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Bob", 1).await, StatusCode::OK);
+        tick(&data).await;
+        let (_, body) = get(&mut app, "/api/getPlayerData").await;
+        let public: Vec<PlayerData> = serde_json::from_str(&body).unwrap();
+        assert_eq!(public[0].vs_player, "Bob");
+        assert_eq!(public[1].vs_player, "Alice");
+    }
+    
+    #[actix_rt::test]
+    async fn load_questions_csv_parses_normal_and_estimation_questions()
+    {
+        let filename = format!("test-{}.csv", std::process::id());
+        fs::write(format!("./Questions/{}", filename), "type,category,question,answer1,answer2,answer3,correct\n\
+            NormalQuestion,Test,\"Which one, really?\",A1,\"A2, quoted\",A3,2\n\
+            EstimationQuestion,Test,How many?,42\n").unwrap();
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        let (status, body) = post(&mut app, "/api/loadQuestionsCsv", json!({"filename": filename})).await;
+        let _ = fs::remove_file(format!("./Questions/{}", filename));
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        assert_eq!(body, "2");
+        
+        let questions = data.questions.read().await;
+        assert!(questions[0].question_type == QuestionType::NormalQuestion);
+        assert_eq!(questions[0].question, "Which one, really?");
+        assert_eq!(questions[0].answers, vec![String::from("A1"), String::from("A2, quoted"), String::from("A3")]);
+        assert_eq!(questions[0].correct_answer, 2);
+        assert!(questions[1].question_type == QuestionType::EstimationQuestion);
+        assert!(questions[1].answers.is_empty());
+        assert_eq!(questions[1].correct_answer, 42);
+    }
+    
+    #[actix_rt::test]
+    async fn lives_mode_eliminates_after_the_last_life()
+    {
+        let mut config = test_config();
+        config.game_mode = String::from("Lives");
+        config.initial_lives = 2;
+        let data = test_data(config, vec![normal_question(1); 5]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        for _ in 0 .. 2
+        {
+            next_question(&mut app, &data).await;
+            assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+            assert_eq!(answer(&mut app, "Bob", 2).await, StatusCode::OK);
+            tick(&data).await;
+        }
+        {
+            let player_data = data.player_data.read().await;
+            assert_eq!((player_data[0].lives, player_data[0].eliminated, player_data[0].money), (2, false, 500));
+            assert_eq!((player_data[1].lives, player_data[1].eliminated, player_data[1].money), (0, true, 500));
+        }
+        
+        //only Alice is left, so the game ends instead of the next question
+        next_question(&mut app, &data).await;
+        assert!(question_state(&data).await == QuestionState::GameEnding);
+    }
+    
+    #[actix_rt::test]
+    async fn replay_sends_the_history_before_new_events()
+    {
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        
+        let mut response = test::call_service(&mut app, test::TestRequest::get().uri("/api/replay").to_request()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut body = response.take_body();
+        let history = String::from_utf8_lossy(&body.next().await.unwrap().unwrap()).into_owned();
+        join(&mut app, "Carol").await;
+        let live = String::from_utf8_lossy(&body.next().await.unwrap().unwrap()).into_owned();
+        
+        let ids: Vec<usize> = format!("{}{}", history, live).lines().filter_map(|line| line.strip_prefix("id: "))
+            .map(|id| id.parse().unwrap()).collect();
+        assert_eq!(ids, vec![0, 1, 2]);
+        assert!(history.contains("event: PlayerJoined") && !history.contains("Carol"));
+        assert!(live.starts_with("id: 2\nevent: PlayerJoined\n") && live.contains("Carol"));
+    }
+    
+    #[actix_rt::test]
+    async fn min_bet_allows_only_an_all_in_below_it()
+    {
+        let question = test_question(json!({"question_type": "BettingQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1, "min_bet": 100}));
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![question]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        let (status, _) = post_admin(&mut app, "/api/setMoney", json!({"name": "Bob", "money": 80}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        next_question(&mut app, &data).await;
+        
+        let (status, _) = get(&mut app, "/api/betMoney?name=Alice&money_bet=50").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = get(&mut app, "/api/betMoney?name=Alice&money_bet=100").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = get(&mut app, "/api/betMoney?name=Bob&money_bet=50").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = get(&mut app, "/api/betMoney?name=Bob&money_bet=80").await;
+        assert_eq!(status, StatusCode::OK);
+        let bets: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money_bet).collect();
+        assert_eq!(bets, vec![100, 80]);
+    }
+    
+    #[actix_rt::test]
+    async fn round_summary_lists_the_correct_answerers_as_winners()
+    {
+        let data = test_data(test_config(), vec![normal_question(2)]);
+        let mut app = test_app(&data).await;
+        for name in ["Alice", "Bob", "Carol"].iter()
+        {
+            join(&mut app, name).await;
+        }
+        next_question(&mut app, &data).await;
+        for (name, given) in ["Alice", "Bob", "Carol"].iter().zip([2, 1, 2].iter())
+        {
+            assert_eq!(answer(&mut app, name, *given).await, StatusCode::OK);
+        }
+        tick(&data).await;
+        
+        let events = data.game_events.read().await;
+        let last = events.last().unwrap();
+        assert_eq!(events[events.len() - 2].event_name, "ShowResults");
+        match &last.event
+        {
+            EventType::RoundSummary(summary) => {
+                assert_eq!(summary.question_index, 1);
+                assert_eq!(summary.correct_answer, 2);
+                assert_eq!(summary.winners, vec![String::from("Alice"), String::from("Carol")]);
+                assert_eq!(summary.biggest_gain.as_ref().map(|(_, gain)| *gain), Some(500));
+            },
+            _ => panic!("RoundSummary expected"),
+        }
+    }
+    
+    #[actix_rt::test]
+    async fn jokers_are_replenished_every_second_question()
+    {
+        let mut config = test_config();
+        config.joker_replenish_every = 2;
+        let data = test_data(config, vec![normal_question(1); 4]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        let mut jokers = Vec::new();
+        for _ in 0 .. 4
+        {
+            next_question(&mut app, &data).await;
+            jokers.push(data.player_data.read().await[0].jokers);
+            assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+            tick(&data).await;
+        }
+        assert_eq!(jokers, vec![INITIAL_JOKERS, INITIAL_JOKERS, INITIAL_JOKERS + 1, INITIAL_JOKERS + 1]);
+    }
+    
+    #[actix_rt::test]
+    async fn fifty_fifty_with_invalid_correct_answer_does_not_panic()
+    {
+        let data = test_data(test_config(), vec![normal_question(0)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        let (status, body) = get(&mut app, "/api/getJokerFiftyFifty?name=Alice").await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(body.contains("correct_answer"));
+        //the server keeps working
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+    }
+    
+    #[actix_rt::test]
+    async fn disabled_player_is_skipped_and_rejected()
+    {
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        let (status, _) = post_admin(&mut app, "/api/setPlayerDisabled", json!({"name": "Bob", "disabled": true}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        next_question(&mut app, &data).await;
+        
+        assert_eq!(answer(&mut app, "Bob", 1).await, StatusCode::LOCKED);
+        let (status, _) = get(&mut app, "/api/getJokerFiftyFifty?name=Bob").await;
+        assert_eq!(status, StatusCode::LOCKED);
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        assert!(question_state(&data).await == QuestionState::NormalQAnswering(true));
+        tick(&data).await;
+        let player_data = data.player_data.read().await;
+        assert_eq!((player_data[0].money, player_data[1].money), (1000, 500));
+        assert_eq!(player_data[1].jokers, INITIAL_JOKERS);
+    }
+    
+    #[actix_rt::test]
+    async fn tied_estimates_split_the_reward_equally()
+    {
+        let mut config = test_config();
+        config.estimation_tie_policy = String::from("SplitEqually");
+        let question = test_question(json!({"question_type": "EstimationQuestion", "category": "Test", "question": "Test question",
+            "answers": [], "correct_answer": 100}));
+        let data = test_data(config, vec![question]);
+        let mut app = test_app(&data).await;
+        for name in ["Alice", "Bob", "Carol"].iter()
+        {
+            join(&mut app, name).await;
+        }
+        next_question(&mut app, &data).await;
+        for (name, given) in ["Alice", "Bob", "Carol"].iter().zip([90, 110, 150].iter())
+        {
+            assert_eq!(answer(&mut app, name, *given).await, StatusCode::OK);
+        }
+        tick(&data).await;
+        
+        let money: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money).collect();
+        assert_eq!(money, vec![500 + 500, 500 + 500, 500]);
+    }
+    
+    #[actix_rt::test]
+    async fn kicked_versus_target_emits_target_missing()
+    {
+        let question = test_question(json!({"question_type": "VersusQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1}));
+        let data = test_data(test_config(), vec![question]);
+        let mut app = test_app(&data).await;
+        for name in ["Alice", "Bob", "Carol"].iter()
+        {
+            join(&mut app, name).await;
+        }
+        next_question(&mut app, &data).await;
+        for (name, vs_player) in [("Alice", "Bob"), ("Bob", "Carol"), ("Carol", "Alice")].iter()
+        {
+            let (status, _) = get(&mut app, &format!("/api/attackPlayer?name={}&vs_player={}", name, vs_player)).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+        tick(&data).await;
+        let (status, _) = get(&mut app, "/api/kickPlayer?name=Bob").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Carol", 2).await, StatusCode::OK);
+        tick(&data).await;
+        
+        let events = data.game_events.read().await;
+        let missing: Vec<(&str, &str)> = events.iter().filter_map(|event| match &event.event
+        {
+            EventType::VersusTargetMissing(missing) => Some((missing.attacker.as_str(), missing.target.as_str())),
+            _ => None,
+        }).collect();
+        assert_eq!(missing, vec![("Alice", "Bob")]);
+        //the attack on the kicked player has no effect, Carol's wrong attack still doubles Alice's money
+        let money: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money).collect();
+        assert_eq!(money, vec![1000, 500]);
+    }
+    
+    #[actix_rt::test]
+    async fn summary_matches_the_results_events()
+    {
+        let data = test_data(test_config(), vec![normal_question(1), normal_question(2)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        for (alice, bob) in [(1, 1), (2, 1)].iter()
+        {
+            next_question(&mut app, &data).await;
+            assert_eq!(answer(&mut app, "Alice", *alice).await, StatusCode::OK);
+            assert_eq!(answer(&mut app, "Bob", *bob).await, StatusCode::OK);
+            tick(&data).await;
+        }
+        
+        let (status, body) = get(&mut app, "/api/summary").await;
+        assert_eq!(status, StatusCode::OK);
+        let summary: SummaryData = serde_json::from_str(&body).unwrap();
+        let events = data.game_events.read().await;
+        let gains: i64 = events.iter().filter_map(|event| match &event.event
+        {
+            EventType::ShowResults(results) => Some(results.player_data.iter().zip(results.previous_player_data.iter())
+                .map(|(player, previous)| (player.money - previous.money).max(0)).sum::<i64>()),
+            _ => None,
+        }).sum();
+        assert_eq!(summary.questions_played, 2);
+        assert_eq!(summary.total_money_awarded, gains);
+        assert_eq!(summary.total_money_awarded, 1500);
+        assert_eq!(summary.jokers_used, 0);
+        assert_eq!(summary.biggest_swing, Some((String::from("Alice"), 500)));
+        assert_eq!(summary.winners, vec![String::from("Alice")]);
+    }
+    
+    #[actix_rt::test]
+    async fn forced_answering_applies_the_default_bet()
+    {
+        let mut config = test_config();
+        config.default_bet = 50;
+        let question = test_question(json!({"question_type": "BettingQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1}));
+        let data = test_data(config, vec![question]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        next_question(&mut app, &data).await;
+        let (status, _) = get(&mut app, "/api/betMoney?name=Alice&money_bet=200").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = get(&mut app, "/api/forceQuestionAnswering").await;
+        assert_eq!(status, StatusCode::OK);
+        
+        let bets: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money_bet).collect();
+        assert_eq!(bets, vec![200, 50]);
+        match &data.game_events.read().await.last().unwrap().event
+        {
+            EventType::DefaultBetApplied(applied) => {
+                assert_eq!(applied.players, vec![String::from("Bob")]);
+                assert_eq!(applied.money_bet, 50);
+            },
+            _ => panic!("DefaultBetApplied expected"),
+        }
+        begin_betting_answering(&data).await;
+        assert_eq!(answer(&mut app, "Alice", 2).await, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Bob", 1).await, StatusCode::OK);
+        tick(&data).await;
+        let money: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money).collect();
+        assert_eq!(money, vec![300, 550]);
+    }
+    
+    #[actix_rt::test]
+    async fn idle_player_is_kicked_after_the_missed_rounds()
+    {
+        let mut config = test_config();
+        config.max_missed_rounds = 2;
+        let data = test_data(config, vec![normal_question(1); 3]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        for round in 0 .. 2
+        {
+            next_question(&mut app, &data).await;
+            assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+            assert_eq!(data.player_data.read().await.len(), 2, "Bob is still there in round {}", round);
+            let (status, _) = get(&mut app, "/api/forceQuestionResults").await;
+            assert_eq!(status, StatusCode::OK);
+            tick(&data).await;
+        }
+        
+        let player_data = data.player_data.read().await;
+        assert_eq!(player_data.len(), 1);
+        assert_eq!(player_data[0].name, "Alice");
+        assert_eq!(player_data[0].missed_rounds, 0);
+        assert!(data.player_index.read().await.get("Bob").is_none());
+        match &data.game_events.read().await.last().unwrap().event
+        {
+            EventType::PlayerLeft(left) => assert_eq!(left.name, "Bob"),
+            _ => panic!("PlayerLeft expected"),
+        };
+        drop(player_data);
+        
+        //nothing changes for the answering Alice, so the roster version stays
+        let roster_version = data.roster_version.load(Ordering::Relaxed);
+        kick_idle_players(&data).await;
+        assert_eq!(data.roster_version.load(Ordering::Relaxed), roster_version);
+    }
+    
+    async fn get_admin<S>(app: &mut S, uri: &str, token: &str) -> (StatusCode, String)
+        where S: Service<Request = actix_http::Request, Response = ServiceResponse, Error = actix_web::Error>
+    {
+        call(app, test::TestRequest::get().uri(uri).header("X-Admin-Token", token).to_request()).await
+    }
+    
+    #[actix_rt::test]
+    async fn admin_question_includes_the_correct_answer()
+    {
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(2), normal_question(4)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        
+        let (status, _) = get(&mut app, "/api/adminQuestion").await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = get_admin(&mut app, "/api/adminQuestion", "wrong").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, body) = get_admin(&mut app, "/api/adminQuestion", "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(serde_json::from_str::<Question>(&body).unwrap().correct_answer, 2);
+        let (status, body) = get_admin(&mut app, "/api/adminQuestion?number=2", "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(serde_json::from_str::<Question>(&body).unwrap().correct_answer, 4);
+        let (status, _) = get_admin(&mut app, "/api/adminQuestion?number=3", "secret").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+    
+    #[actix_rt::test]
+    async fn bets_locked_precedes_the_betting_question()
+    {
+        let question = test_question(json!({"question_type": "BettingQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1}));
+        let data = test_data(test_config(), vec![question]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        let (status, _) = get(&mut app, "/api/betMoney?name=Alice&money_bet=100").await;
+        assert_eq!(status, StatusCode::OK);
+        begin_betting_answering(&data).await;
+        
+        let names = event_names(&data).await;
+        assert_eq!(names[names.len() - 2 ..].to_vec(), vec![String::from("BetsLocked"), String::from("BeginBettingQAnswering")]);
+    }
+    
+    #[actix_rt::test]
+    async fn post_answer_question_behaves_like_get()
+    {
+        let mut results = Vec::new();
+        for &use_post in [false, true].iter()
+        {
+            let data = test_data(test_config(), vec![normal_question(1)]);
+            let mut app = test_app(&data).await;
+            join(&mut app, "Alice").await;
+            join(&mut app, "Bob").await;
+            next_question(&mut app, &data).await;
+            let mut statuses = Vec::new();
+            for (name, given) in [("Alice", 0), ("Nobody", 1), ("Alice", 1), ("Bob", 2)].iter()
+            {
+                let status = if use_post
+                {
+                    post(&mut app, "/api/answerQuestion", json!({"name": name, "answer": given})).await.0
+                }
+                else
+                {
+                    answer(&mut app, name, *given).await
+                };
+                statuses.push(status);
+            }
+            tick(&data).await;
+            let money: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money).collect();
+            results.push((statuses, money));
+        }
+        assert_eq!(results[0].0, vec![StatusCode::BAD_REQUEST, StatusCode::BAD_REQUEST, StatusCode::OK, StatusCode::OK]);
+        assert_eq!(results[0].1, vec![1000, 500]);
+        assert_eq!(results[0], results[1]);
+    }
+    
+    #[actix_rt::test]
+    async fn state_carries_the_server_time()
+    {
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        let before = unix_time_ms();
+        let (status, body) = get(&mut app, "/api/state").await;
+        let after = unix_time_ms();
+        assert_eq!(status, StatusCode::OK);
+        let state: serde_json::Value = serde_json::from_str(&body).unwrap();
+        let server_time = state["server_time_unix_ms"].as_u64().unwrap();
+        assert!(before <= server_time && server_time <= after);
+    }
+    
+    #[actix_rt::test]
+    async fn remaining_questions_list_only_types_and_categories()
+    {
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let questions = vec![normal_question(1),
+            test_question(json!({"question_type": "EstimationQuestion", "category": "Numbers", "question": "Secret", "answers": [], "correct_answer": 7})),
+            test_question(json!({"question_type": "BettingQuestion", "category": "Bets", "question": "Secret", "answers": ["A1", "A2"], "correct_answer": 1})),
+            test_question(json!({"question_type": "VersusQuestion", "category": "Duel", "question": "Secret", "answers": ["A1", "A2"], "correct_answer": 2}))];
+        let data = test_data(config, questions);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        
+        let (status, _) = get(&mut app, "/api/remainingQuestions").await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, body) = get_admin(&mut app, "/api/remainingQuestions", "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(!body.contains("Secret"));
+        let remaining: Vec<RemainingQuestionData> = serde_json::from_str(&body).unwrap();
+        let summary: Vec<(usize, QuestionType, &str)> = remaining.iter()
+            .map(|question| (question.index, question.question_type, question.category.as_str())).collect();
+        assert!(summary == vec![(2, QuestionType::EstimationQuestion, "Numbers"), (3, QuestionType::BettingQuestion, "Bets"),
+            (4, QuestionType::VersusQuestion, "Duel")]);
+    }
+    
+    #[actix_rt::test]
+    async fn versus_rounding_of_halved_odd_money()
+    {
+        let mut halved = Vec::new();
+        for rounding in ["Truncate", "Round", "Ceil"].iter()
+        {
+            let mut config = test_config();
+            config.versus_rounding = String::from(*rounding);
+            let question = test_question(json!({"question_type": "VersusQuestion", "category": "Test", "question": "Test question",
+                "answers": ["A1", "A2"], "correct_answer": 1}));
+            config.admin_token = Some(String::from("secret"));
+            let data = test_data(config, vec![question]);
+            let mut app = test_app(&data).await;
+            join(&mut app, "Alice").await;
+            join(&mut app, "Bob").await;
+            let (status, _) = post_admin(&mut app, "/api/setMoney", json!({"name": "Bob", "money": 101}), "secret").await;
+            assert_eq!(status, StatusCode::OK);
+            next_question(&mut app, &data).await;
+            let (status, _) = get(&mut app, "/api/attackPlayer?name=Alice&vs_player=Bob").await;
+            assert_eq!(status, StatusCode::OK);
+            let (status, _) = get(&mut app, "/api/forceQuestionAnswering").await;
+            assert_eq!(status, StatusCode::OK);
+            tick(&data).await;
+            assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+            assert_eq!(answer(&mut app, "Bob", 1).await, StatusCode::OK);
+            tick(&data).await;
+            halved.push(data.player_data.read().await[1].money);
+        }
+        assert_eq!(halved, vec![50, 51, 51]);
+    }
+    
+    #[actix_rt::test]
+    async fn only_allowed_bets_are_accepted()
+    {
+        let mut config = test_config();
+        config.allowed_bets = vec![50, 100];
+        let question = test_question(json!({"question_type": "BettingQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1}));
+        let data = test_data(config, vec![question]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        
+        let (status, body) = get(&mut app, "/api/betMoney?name=Alice&money_bet=75").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("50, 100"));
+        let (status, _) = get(&mut app, "/api/betMoney?name=Alice&money_bet=100").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(data.player_data.read().await[0].money_bet, 100);
+    }
+    
+    #[actix_rt::test]
+    async fn recompute_results_corrects_the_money()
+    {
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 2).await, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Bob", 1).await, StatusCode::OK);
+        tick(&data).await;
+        let money: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money).collect();
+        assert_eq!(money, vec![500, 1000]);
+        
+        let (status, _) = post_admin(&mut app, "/api/fixCorrectAnswer", json!({"number": 1, "correct_answer": 2}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = post_admin(&mut app, "/api/recomputeResults", json!({}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        let player_data = data.player_data.read().await;
+        let money: Vec<i64> = player_data.iter().map(|player| player.money).collect();
+        assert_eq!(money, vec![1000, 500]);
+        assert_eq!((player_data[0].correct_count, player_data[1].correct_count), (1, 0));
+        match &data.game_events.read().await.iter().rev().find(|event| event.event_name == "ShowResults").unwrap().event
+        {
+            EventType::ShowResults(results) => {
+                assert_eq!(results.correct_answer, 2);
+                assert_eq!(results.previous_player_data[1].money, 500);
+            },
+            _ => panic!("ShowResults expected"),
+        };
+    }
+    
+    #[actix_rt::test]
+    async fn betting_answering_event_has_the_question_index()
+    {
+        let betting = test_question(json!({"question_type": "BettingQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1}));
+        let data = test_data(test_config(), vec![normal_question(1), betting]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        next_question(&mut app, &data).await;
+        let (status, _) = get(&mut app, "/api/betMoney?name=Alice&money_bet=100").await;
+        assert_eq!(status, StatusCode::OK);
+        begin_betting_answering(&data).await;
+        
+        let events = data.game_events.read().await;
+        let betting_question = events.iter().find_map(|event| match &event.event
+        {
+            EventType::BeginBettingQBetting(betting) => Some(betting.current_question),
+            _ => None,
+        });
+        match &events.last().unwrap().event
+        {
+            EventType::BeginBettingQAnswering(answering) => {
+                assert_eq!(Some(answering.current_question), betting_question);
+                assert_eq!(answering.current_question, 2);
+                assert_eq!(answering.num_questions, 2);
+            },
+            _ => panic!("BeginBettingQAnswering expected"),
+        }
+    }
+    
+    #[actix_rt::test]
+    async fn game_end_waits_for_the_host_confirmation()
+    {
+        let mut config = test_config();
+        config.confirm_game_end = true;
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        next_question(&mut app, &data).await;
+        
+        assert!(question_state(&data).await == QuestionState::AwaitingGameEnd);
+        tick(&data).await;
+        assert!(question_state(&data).await == QuestionState::AwaitingGameEnd);
+        let names = event_names(&data).await;
+        assert_eq!(names.last().map(String::as_str), Some("ReadyToEndGame"));
+        assert!(!names.contains(&String::from("GameEnding")));
+        
+        let (status, _) = post(&mut app, "/api/confirmGameEnd", json!({})).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(question_state(&data).await == QuestionState::GameEnding);
+        assert_eq!(event_names(&data).await.last().map(String::as_str), Some("GameEnding"));
+    }
+    
+    //write a question file for the test into the questions folder, returns the file name
+    fn question_file(name: &str, content: &str) -> String
+    {
+        let filename = format!("test-{}-{}", std::process::id(), name);
+        fs::write(format!("./Questions/{}", filename), content).unwrap();
+        filename
+    }
+    
+    #[actix_rt::test]
+    async fn load_questions_filtered_keeps_only_tagged_questions()
+    {
+        let filename = question_file("tags.json", &json!([
+            {"question_type": "NormalQuestion", "category": "Football", "question": "Q1", "answers": ["A1", "A2"], "correct_answer": 1, "tags": ["sports", "easy"]},
+            {"question_type": "NormalQuestion", "category": "Movies", "question": "Q2", "answers": ["A1", "A2"], "correct_answer": 1, "tags": ["easy"]},
+            {"question_type": "NormalQuestion", "category": "Tennis", "question": "Q3", "answers": ["A1", "A2"], "correct_answer": 2, "tags": ["sports"]},
+            {"question_type": "NormalQuestion", "category": "Music", "question": "Q4", "answers": ["A1", "A2"], "correct_answer": 2}
+        ]).to_string());
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        let (status, body) = post(&mut app, "/api/loadQuestionsFiltered", json!({"filename": filename, "tags": ["sports"]})).await;
+        let (empty_status, _) = post(&mut app, "/api/loadQuestionsFiltered", json!({"filename": filename, "tags": ["history"]})).await;
+        let _ = fs::remove_file(format!("./Questions/{}", filename));
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        assert_eq!(body, "2");
+        assert_eq!(empty_status, StatusCode::BAD_REQUEST);
+        
+        let categories: Vec<String> = data.questions.read().await.iter().map(|question| question.category.clone()).collect();
+        assert_eq!(categories, vec![String::from("Football"), String::from("Tennis")]);
+    }
+    
+    #[actix_rt::test]
+    async fn shuffle_remaining_keeps_the_played_questions()
+    {
+        let mut config = test_config();
+        config.rng_seed = Some(7);
+        let questions: Vec<Question> = (1 ..= 5).map(|i| test_question(json!({"question_type": "NormalQuestion", "category": "Test",
+            "question": format!("Q{}", i), "answers": ["A1", "A2"], "correct_answer": 1}))).collect();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, questions);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        for _ in 0 .. 2
+        {
+            next_question(&mut app, &data).await;
+            assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+            tick(&data).await;
+        }
+        
+        let (status, _) = post_admin(&mut app, "/api/shuffleRemaining", json!({}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(data.current_question.load(Ordering::Relaxed), 2);
+        let order: Vec<String> = data.questions.read().await.iter().map(|question| question.question.clone()).collect();
+        assert_eq!(order[.. 2].to_vec(), vec![String::from("Q1"), String::from("Q2")]);
+        let mut remaining = order[2 ..].to_vec();
+        remaining.sort();
+        assert_eq!(remaining, vec![String::from("Q3"), String::from("Q4"), String::from("Q5")]);
+    }
+    
+    //end the game with the given money of the players and return the winners and the winning money
+    async fn game_ending_winners(money: &[i64]) -> (Vec<String>, i64)
+    {
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        for (i, money) in money.iter().enumerate()
+        {
+            let name = format!("Player{}", i + 1);
+            join(&mut app, &name).await;
+            let (status, _) = post_admin(&mut app, "/api/setMoney", json!({"name": name, "money": money}), "secret").await;
+            assert_eq!(status, StatusCode::OK);
+        }
+        let (status, _) = post_admin(&mut app, "/api/endGame", json!({}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        let events = data.game_events.read().await;
+        match &events.last().unwrap().event
+        {
+            EventType::GameEnding(ending) => (ending.winners.clone(), ending.winning_money),
+            _ => panic!("GameEnding expected"),
+        }
+    }
+    
+    #[actix_rt::test]
+    async fn game_ending_names_the_winners()
+    {
+        assert_eq!(game_ending_winners(&[300, 900, 600]).await, (vec![String::from("Player2")], 900));
+        assert_eq!(game_ending_winners(&[800, 200, 800]).await, (vec![String::from("Player1"), String::from("Player3")], 800));
+    }
+    
+    #[actix_rt::test]
+    async fn soft_kicked_player_is_kept_after_reconnecting()
+    {
+        let mut config = test_config();
+        config.kick_grace_secs = 30;
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        let (status, _) = get(&mut app, "/api/joinPlayer?name=Bob&pin=42").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = get(&mut app, "/api/kickPlayer?name=Bob").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(data.player_data.read().await[1].pending_removal.is_some());
+        
+        let (status, _) = get(&mut app, "/api/joinPlayer?name=Bob&pin=41").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert!(data.player_data.read().await[1].pending_removal.is_some());
+        let (status, body) = get(&mut app, "/api/joinPlayer?name=Bob&pin=42").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(serde_json::from_str::<JoinPlayerResult>(&body).unwrap().reconnected);
+        assert!(data.player_data.read().await[1].pending_removal.is_none());
+        remove_kicked_players(&data).await;
+        assert_eq!(data.player_data.read().await.len(), 2);
+        assert!(!event_names(&data).await.contains(&String::from("PlayerLeft")));
+        
+        //a hard kick still removes the player immediately
+        let (status, _) = get(&mut app, "/api/kickPlayer?name=Bob&hard=true").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(data.player_data.read().await.len(), 1);
+    }
+    
+    #[actix_rt::test]
+    async fn downloaded_questions_load_again()
+    {
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, Vec::new());
+        let mut app = test_app(&data).await;
+        let (status, body) = post(&mut app, "/api/loadQuestions", json!({"filename": "questions-example.json"})).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        let count = data.questions.read().await.len();
+        
+        let (status, _) = get(&mut app, "/api/currentQuestions").await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, downloaded) = get_admin(&mut app, "/api/currentQuestions", "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        let questions: Vec<Question> = serde_json::from_str(&downloaded).unwrap();
+        assert_eq!(questions.len(), count);
+        assert!(validate_questions(&questions).is_ok());
+        
+        let filename = question_file("download.json", &downloaded);
+        let (status, body) = post(&mut app, "/api/loadQuestions", json!({"filename": filename})).await;
+        let _ = fs::remove_file(format!("./Questions/{}", filename));
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, count.to_string());
+        let (_, reloaded) = get_admin(&mut app, "/api/currentQuestions", "secret").await;
+        assert_eq!(reloaded, downloaded);
+    }
+    
+    #[actix_rt::test]
+    async fn safe_money_limits_the_betting_loss()
+    {
+        let mut config = test_config();
+        config.safe_money = 100;
+        let question = test_question(json!({"question_type": "BettingQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1}));
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![question]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        let (status, _) = get(&mut app, "/api/betMoney?name=Alice&money_bet=300").await;
+        assert_eq!(status, StatusCode::OK);
+        begin_betting_answering(&data).await;
+        let (status, _) = post_admin(&mut app, "/api/setMoney", json!({"name": "Alice", "money": 250}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Alice", 2).await, StatusCode::OK);
+        tick(&data).await;
+        
+        assert_eq!(data.player_data.read().await[0].money, 100);
+    }
+    
+    #[actix_rt::test]
+    async fn transitions_are_recorded()
+    {
+        let data = test_data(test_config(), vec![normal_question(1), normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        let before = unix_time_ms();
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        next_question(&mut app, &data).await;
+        
+        let (status, body) = get(&mut app, "/api/transitions").await;
+        assert_eq!(status, StatusCode::OK);
+        let transitions: Vec<TransitionData> = serde_json::from_str(&body).unwrap();
+        let states: Vec<(&str, bool)> = transitions.iter().map(|transition| (transition.state.as_str(), transition.ready)).collect();
+        assert_eq!(states, vec![("NormalQAnswering", false), ("Results", false), ("NormalQAnswering", false)]);
+        assert!(transitions.windows(2).all(|pair| pair[0].time_unix_ms <= pair[1].time_unix_ms));
+        assert!(transitions[0].time_unix_ms >= before);
+    }
+    
+    #[actix_rt::test]
+    async fn round_completes_without_disabled_and_skipped_players()
+    {
+        let mut config = test_config();
+        config.kick_grace_secs = 30;
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        for name in ["Alice", "Disabled", "Kicked", "OutOfTime"].iter()
+        {
+            join(&mut app, name).await;
+        }
+        let (status, _) = post_admin(&mut app, "/api/setPlayerDisabled", json!({"name": "Disabled", "disabled": true}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = get(&mut app, "/api/kickPlayer?name=Kicked").await;
+        assert_eq!(status, StatusCode::OK);
+        data.player_data.write().await[3].time_budget_ms = Some(0);
+        next_question(&mut app, &data).await;
+        
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        assert!(question_state(&data).await == QuestionState::NormalQAnswering(true));
+        tick(&data).await;
+        assert!(question_state(&data).await == QuestionState::Results(false));
+        let money: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money).collect();
+        assert_eq!(money, vec![1000, 500, 500, 500]);
+    }
+    
+    #[actix_rt::test]
+    async fn estimation_results_have_the_correct_value_and_explanation()
+    {
+        let question = test_question(json!({"question_type": "EstimationQuestion", "category": "Test", "question": "Test question",
+            "answers": [], "correct_answer": 3, "correct_answer_f": 3.5, "explanation": "Three and a half"}));
+        let data = test_data(test_config(), vec![question]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 4).await, StatusCode::OK);
+        tick(&data).await;
+        
+        let events = data.game_events.read().await;
+        let results = events.iter().find(|event| event.event_name == "ShowResults").unwrap();
+        let results = serde_json::to_value(results).unwrap();
+        let results = &results["event"]["ShowResults"];
+        assert_eq!(results["correct_answer_f"], json!(3.5));
+        assert_eq!(results["explanation"], json!("Three and a half"));
+    }
+    
+    #[actix_rt::test]
+    async fn peek_next_reports_the_game_end()
+    {
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let betting = test_question(json!({"question_type": "BettingQuestion", "category": "Bets", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1}));
+        let data = test_data(config, vec![normal_question(1), betting]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        
+        let (status, _) = get(&mut app, "/api/peekNext").await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, body) = get_admin(&mut app, "/api/peekNext", "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        let next: PeekNextData = serde_json::from_str(&body).unwrap();
+        assert!(!next.game_ends);
+        assert_eq!(next.index, Some(2));
+        assert!(next.question_type == Some(QuestionType::BettingQuestion));
+        assert_eq!(next.category.as_deref(), Some("Bets"));
+        
+        next_question(&mut app, &data).await;
+        let (status, _) = get(&mut app, "/api/forceQuestionAnswering").await;
+        assert_eq!(status, StatusCode::OK);
+        begin_betting_answering(&data).await;
+        let (status, _) = get(&mut app, "/api/forceQuestionResults").await;
+        assert_eq!(status, StatusCode::OK);
+        tick(&data).await;
+        let (_, body) = get_admin(&mut app, "/api/peekNext", "secret").await;
+        let next: PeekNextData = serde_json::from_str(&body).unwrap();
+        assert!(next.game_ends);
+        assert_eq!(next.index, None);
+    }
+    
+    #[actix_rt::test]
+    async fn bet_speed_bonus_goes_to_the_faster_correct_bettor()
+    {
+        let question = test_question(json!({"question_type": "BettingQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1, "bet_speed_bonus": 100}));
+        let data = test_data(test_config(), vec![question]);
+        let mut app = test_app(&data).await;
+        for name in ["Slow", "Fast"].iter()
+        {
+            join(&mut app, name).await;
+        }
+        next_question(&mut app, &data).await;
+        for name in ["Slow", "Fast"].iter()
+        {
+            let (status, _) = get(&mut app, &format!("/api/betMoney?name={}&money_bet=100", name)).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+        begin_betting_answering(&data).await;
+        assert_eq!(answer(&mut app, "Fast", 1).await, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Slow", 1).await, StatusCode::OK);
+        tick(&data).await;
+        
+        let money: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money).collect();
+        assert_eq!(money, vec![500 + 100, 500 + 100 + 100]);
+    }
+    
+    #[actix_rt::test]
+    async fn doubling_money_stops_at_max_money()
     {
-        let question_state = data.current_question_state.read().await;
-        if *question_state != QuestionState::NormalQAnswering(false) &&
-            *question_state != QuestionState::BettingQAnswering(false) &&
-            *question_state != QuestionState::EstimationQAnswering(false) &&
-            *question_state != QuestionState::VersusQAnswering(false)
+        let mut config = test_config();
+        config.max_money = 5000;
+        let question = test_question(json!({"question_type": "BettingQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1}));
+        let data = test_data(config, vec![question; 4]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        let mut money = Vec::new();
+        for _ in 0 .. 4
         {
-            return HttpResponse::NotAcceptable().body("QuestionState is not *Answering(false)!");
+            next_question(&mut app, &data).await;
+            let all_in = data.player_data.read().await[0].money;
+            let (status, _) = get(&mut app, &format!("/api/betMoney?name=Alice&money_bet={}", all_in)).await;
+            assert_eq!(status, StatusCode::OK);
+            begin_betting_answering(&data).await;
+            assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+            tick(&data).await;
+            money.push(data.player_data.read().await[0].money);
         }
+        assert_eq!(money, vec![1000, 2000, 4000, 5000]);
+        
+        let (_, body) = post(&mut app, "/api/giveMoney", json!({"name": "Alice", "money": i64::MAX})).await;
+        assert_eq!(serde_json::from_str::<GiveMoneyData>(&body).unwrap().money, 5000);
     }
     
-    //perform answering
+    #[actix_rt::test]
+    async fn fun_fact_is_only_in_results_that_have_one()
     {
-        if params.answer < 1
+        let with_fact = test_question(json!({"question_type": "NormalQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1, "fun_fact": "Did you know?"}));
+        let data = test_data(test_config(), vec![with_fact, normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        for _ in 0 .. 2
         {
-            return HttpResponse::BadRequest().body("answer is invalid (< 1)!");
+            next_question(&mut app, &data).await;
+            assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+            tick(&data).await;
         }
         
-        let mut name_found = false;
-        let mut access = data.player_data.write().await;
-        for player in (*access).iter_mut()
+        let events = data.game_events.read().await;
+        let results: Vec<&EventShowResults> = events.iter().filter_map(|event| match &event.event
         {
-            if player.name == params.name
-            { //set player's answer
-                player.answer = params.answer;
-                name_found = true;
-                break;
-            }
-        }
-        if !name_found
+            EventType::ShowResults(results) => Some(results),
+            _ => None,
+        }).collect();
+        assert_eq!(results[0].fun_fact.as_deref(), Some("Did you know?"));
+        assert_eq!(results[1].fun_fact, None);
+        assert!(!serde_json::to_string(results[1]).unwrap().contains("fun_fact"));
+    }
+    
+    #[actix_rt::test]
+    async fn fixed_correct_answer_is_used_for_the_scoring()
+    {
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(1), normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        
+        let (status, _) = post_admin(&mut app, "/api/fixCorrectAnswer", json!({"number": 2, "correct_answer": 5}), "secret").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = post_admin(&mut app, "/api/fixCorrectAnswer", json!({"number": 3, "correct_answer": 1}), "secret").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = post_admin(&mut app, "/api/fixCorrectAnswer", json!({"number": 2, "correct_answer": 3}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(data.questions.read().await[1].correct_answer, 3);
+        next_question(&mut app, &data).await;
+        let (status, _) = post_admin(&mut app, "/api/fixCorrectAnswer", json!({"number": 2, "correct_answer": 1}), "secret").await;
+        assert_eq!(status, StatusCode::NOT_ACCEPTABLE);
+        assert_eq!(answer(&mut app, "Alice", 3).await, StatusCode::OK);
+        tick(&data).await;
+        assert_eq!(data.player_data.read().await[0].money, 1500);
+    }
+    
+    #[actix_rt::test]
+    async fn steal_question_transfers_money_from_the_wrong_player()
+    {
+        let mut config = test_config();
+        config.rng_seed = Some(1);
+        let question = test_question(json!({"question_type": "NormalQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1, "steal": true}));
+        let data = test_data(config, vec![question]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Bob", 2).await, StatusCode::OK);
+        tick(&data).await;
+        
+        let money: Vec<i64> = data.player_data.read().await.iter().map(|player| player.money).collect();
+        assert_eq!(money, vec![500 + 500 + STEAL_AMOUNT, 500 - STEAL_AMOUNT]);
+    }
+    
+    #[actix_rt::test]
+    async fn announcement_is_sanitized_into_an_event()
+    {
+        let mut config = test_config();
+        config.announcement_max_length = 12;
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        let (status, body) = post_admin(&mut app, "/api/announce", json!({"message": " Break\u{7} in 5\n minutes, please return"}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(serde_json::from_str::<AnnounceData>(&body).unwrap().message, "Break in 5");
+        let (status, _) = post_admin(&mut app, "/api/announce", json!({"message": "\n\t "}), "secret").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        
+        let (_, body) = get(&mut app, "/api/getGameEvents?type=Announcement").await;
+        let events: Vec<Event> = serde_json::from_str(&body).unwrap();
+        assert_eq!(events.len(), 1);
+        match &events[0].event
         {
-            return HttpResponse::BadRequest().body("Player name was not found!");
+            EventType::Announcement(announcement) => assert_eq!(announcement.message, "Break in 5"),
+            _ => panic!("Announcement expected"),
         }
     }
     
-    //check if all players have answered to indicate abilitiy to proceed
-    let mut all_answered = true;
+    #[actix_rt::test]
+    async fn elimination_order_is_recorded()
     {
-        let access = data.player_data.read().await;
-        for player in (*access).iter()
+        let mut config = test_config();
+        config.game_mode = String::from("Lives");
+        config.initial_lives = 1;
+        let data = test_data(config, vec![normal_question(1); 3]);
+        let mut app = test_app(&data).await;
+        for name in ["Alice", "Bob", "Carol"].iter()
+        {
+            join(&mut app, name).await;
+        }
+        for wrong in ["Bob", "Carol"].iter()
         {
-            if player.answer < 1
+            next_question(&mut app, &data).await;
+            let names: Vec<String> = data.player_data.read().await.iter().filter(|player| !player.eliminated).map(|player| player.name.clone()).collect();
+            for name in names.iter()
             {
-                all_answered = false;
-                break;
+                let given = if name == wrong { 2 } else { 1 };
+                assert_eq!(answer(&mut app, name, given).await, StatusCode::OK);
             }
+            tick(&data).await;
         }
+        
+        let (_, body) = get(&mut app, "/api/getPlayerData").await;
+        let players: Vec<PlayerData> = serde_json::from_str(&body).unwrap();
+        let order: Vec<(bool, Option<usize>)> = players.iter().map(|player| (player.eliminated, player.elimination_order)).collect();
+        assert_eq!(order, vec![(false, None), (true, Some(1)), (true, Some(2))]);
     }
-    if all_answered
+    
+    #[actix_rt::test]
+    async fn audience_poll_follows_the_given_answers()
     {
-        let mut question_state = data.current_question_state.write().await;
-        match *question_state
+        let mut config = test_config();
+        config.audience_poll_from_answers = true;
+        let data = test_data(config, vec![normal_question(2)]);
+        let mut app = test_app(&data).await;
+        for name in ["Alice", "Bob", "Carol", "Dave"].iter()
         {
-            QuestionState::NormalQAnswering(_) => { *question_state = QuestionState::NormalQAnswering(true); },
-            QuestionState::BettingQAnswering(_) => { *question_state = QuestionState::BettingQAnswering(true); },
-            QuestionState::EstimationQAnswering(_) => { *question_state = QuestionState::EstimationQAnswering(true); },
-            QuestionState::VersusQAnswering(_) => { *question_state = QuestionState::VersusQAnswering(true); },
-            _ => {},
+            join(&mut app, name).await;
         }
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Bob", 2).await, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Carol", 2).await, StatusCode::OK);
+        let (status, _) = get(&mut app, "/api/getJokerAudiencePoll?name=Alice").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(answer(&mut app, "Dave", 3).await, StatusCode::OK);
+        
+        let (status, body) = get(&mut app, "/api/getJokerAudiencePoll?name=Alice").await;
+        assert_eq!(status, StatusCode::OK);
+        //votes 2, 2, 3 with one smoothing vote for every answer: 1, 3, 2, 1 of 7
+        let percentages: Vec<usize> = serde_json::from_str(&body).unwrap();
+        assert_eq!(percentages, vec![14, 43, 29, 14]);
+        assert_eq!(data.player_data.read().await[0].jokers, INITIAL_JOKERS - 1);
     }
     
-    HttpResponse::Ok().finish()
-}
-
-//get 50/50 joker for current question (only for betting questions!)
-#[derive(Serialize, Deserialize)]
-struct GetJokerData
-{
-    name: String,
-}
-#[get("/api/getJokerFiftyFifty")]
-async fn get_joker_fifty_fifty(data: web::Data<GameshowData>, params: web::Query<GetJokerData>) -> impl Responder
-{
-    //ensure current question state is answering for normal or betting question, else return not acceptable
+    #[actix_rt::test]
+    async fn global_timeout_advances_the_round()
     {
-        let question_state = data.current_question_state.read().await;
-        if *question_state != QuestionState::NormalQAnswering(false) &&
-            *question_state != QuestionState::BettingQAnswering(false)
-        {
-            return HttpResponse::NotAcceptable().body("QuestionState is not BettingQAnswering(false)!");
-        }
+        let mut config = test_config();
+        config.global_question_timeout_secs = 10;
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        tick(&data).await;
+        assert!(question_state(&data).await == QuestionState::NormalQAnswering(false));
+        
+        *data.state_began_at.lock().await = Instant::now() - Duration::from_secs(11);
+        tick(&data).await;
+        tick(&data).await;
+        assert!(question_state(&data).await == QuestionState::Results(false));
+        assert_eq!(data.player_data.read().await[0].money, 500);
     }
     
-    //get wrong answers
-    let wrong_answers: Vec<usize>;
+    #[actix_rt::test]
+    async fn my_options_exclude_the_removed_answers()
     {
-        let mut rng = rand::thread_rng();
-        let current_question = data.current_question.load(Ordering::Relaxed);
-        let questions = data.questions.read().await;
-        let correct_answer = (*questions)[current_question - 1].correct_answer;
-        let mut choose_from = vec![1, 2, 3, 4];
-        choose_from.remove(correct_answer - 1); //removed by index
-        wrong_answers = choose_from.choose_multiple(&mut rng, 2).copied().collect();
+        let data = test_data(test_config(), vec![normal_question(3)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        let (status, _) = get(&mut app, "/api/joinPlayer?name=Bob&pin=7").await;
+        assert_eq!(status, StatusCode::OK);
+        next_question(&mut app, &data).await;
+        let (_, body) = get(&mut app, "/api/myOptions?name=Alice").await;
+        assert_eq!(serde_json::from_str::<Vec<usize>>(&body).unwrap(), vec![1, 2, 3, 4]);
+        
+        let (_, body) = get(&mut app, "/api/getJokerFiftyFifty?name=Alice").await;
+        let removed: Vec<usize> = serde_json::from_str(&body).unwrap();
+        let (status, body) = get(&mut app, "/api/myOptions?name=Alice").await;
+        assert_eq!(status, StatusCode::OK);
+        let options: Vec<usize> = serde_json::from_str(&body).unwrap();
+        assert_eq!(options.len(), 2);
+        assert!(options.contains(&3));
+        assert!(options.iter().all(|option| !removed.contains(option)));
+        
+        let (status, _) = get(&mut app, "/api/myOptions?name=Bob").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (_, body) = get(&mut app, "/api/myOptions?name=Bob&pin=7").await;
+        assert_eq!(serde_json::from_str::<Vec<usize>>(&body).unwrap(), vec![1, 2, 3, 4]);
     }
     
-    //send wrong answers
-    let mut access = data.player_data.write().await;
-    for player in (*access).iter_mut()
+    #[actix_rt::test]
+    async fn tie_at_the_end_is_resolved_by_a_coin_flip()
     {
-        if player.name == params.name
-        { //set player's answer
-            if player.jokers < 1
-            {
-                return HttpResponse::NotAcceptable().body("No jokers available!");
-            }
-            else
-            {
-                player.jokers -= 1;
-                return HttpResponse::Ok().json(wrong_answers);
-            }
-        }
+        let mut config = test_config();
+        config.tiebreaker_coin_flip = true;
+        config.rng_seed = Some(5);
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        next_question(&mut app, &data).await;
+        //no one answers the last question, so both stay tied
+        let (status, _) = get(&mut app, "/api/forceQuestionResults").await;
+        assert_eq!(status, StatusCode::OK);
+        tick(&data).await;
+        next_question(&mut app, &data).await;
+        
+        let events = data.game_events.read().await;
+        let names: Vec<&str> = events.iter().map(|event| event.event_name.as_str()).collect();
+        assert_eq!(names[names.len() - 2 ..].to_vec(), vec!["GameEnding", "TiebreakerResolved"]);
+        match &events.last().unwrap().event
+        {
+            EventType::TiebreakerResolved(resolved) => {
+                assert!(resolved.winner == "Alice" || resolved.winner == "Bob");
+                assert_eq!(resolved.method, "CoinFlip");
+            },
+            _ => panic!("TiebreakerResolved expected"),
+        };
     }
     
-    HttpResponse::BadRequest().body("Player name was not found!")
-}
-
-//get current status and game commands
-#[get("/api/getGameEvents")]
-async fn get_game_events(data: web::Data<GameshowData>) -> impl Responder
-{
-    check_state_add_events(data.clone()).await;
-    
-    let access = data.game_events.read().await;
-    let data = (*access).clone();
-    
-    HttpResponse::Ok().json(data)
-}
-
-//give a player money, minus value to remove money
-#[derive(Serialize, Deserialize)]
-struct GiveMoneyData
-{
-    name: String,
-    money: i64,
-}
-#[post("/api/giveMoney")]
-async fn give_money(data: web::Data<GameshowData>, params: web::Json<GiveMoneyData>) -> impl Responder
-{
-    let mut access = data.player_data.write().await;
-    
-    for player in (*access).iter_mut()
+    //read the configuration with the given ENV variables set (and removed again afterwards)
+    fn config_with_env(vars: &[(&str, &str)]) -> Result<Config, String>
     {
-        if player.name == params.name
+        let _lock = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        for (name, value) in vars.iter()
         {
-            player.money += params.money;
-            return HttpResponse::Ok().json(GiveMoneyData {name: player.name.clone(), money: player.money});
+            env::set_var(name, value);
         }
+        let config = Config::from_env();
+        for (name, _) in vars.iter()
+        {
+            env::remove_var(name);
+        }
+        config
     }
     
-    HttpResponse::BadRequest().body("Player name was not found!")
-}
-
-//set a player's number of available jokers
-#[derive(Serialize, Deserialize)]
-struct SetJokersData
-{
-    name: String,
-    jokers: usize,
-}
-#[post("/api/setJokers")]
-async fn set_jokers(data: web::Data<GameshowData>, params: web::Json<SetJokersData>) -> impl Responder
-{
-    let mut access = data.player_data.write().await;
+    #[test]
+    fn config_is_read_and_validated_from_env()
+    {
+        let config = config_with_env(&[("NORMAL_Q_MONEY", "750"), ("GAME_MODE", "Lives"), ("ALLOWED_BETS", "50, 100"),
+            ("CATEGORY_MULTIPLIERS", "{\"Science\": 2.0}")]).unwrap();
+        assert_eq!(config.normal_q_money, 750);
+        assert_eq!(config.game_mode, "Lives");
+        assert_eq!(config.allowed_bets, vec![50, 100]);
+        assert_eq!(config.category_multipliers.get("Science"), Some(&2.0));
+        
+        assert_eq!(config_with_env(&[("INITIAL_MONEY", "lots")]).err().unwrap(), "INITIAL_MONEY has the invalid value \"lots\"!");
+        assert!(config_with_env(&[("NORMAL_Q_MONEY", "-5")]).err().unwrap().contains("NORMAL_Q_MONEY"));
+        assert_eq!(config_with_env(&[("GAME_MODE", "Chaos")]).err().unwrap(), "GAME_MODE must be one of Money, Lives!");
+        assert!(config_with_env(&[("INITIAL_MONEY", "0"), ("MONEY_FLOOR", "1")]).err().unwrap().contains("INITIAL_MONEY"));
+        assert!(config_with_env(&[]).is_ok());
+    }
     
-    for player in (*access).iter_mut()
+    #[actix_rt::test]
+    async fn start_question_begins_the_inline_question()
     {
-        if player.name == params.name
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(1), normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        let (status, _) = post_admin(&mut app, "/api/startQuestion", json!({"question_type": "NormalQuestion", "category": "Live",
+            "question": "Inline question", "answers": ["A1", "A2"], "correct_answer": 2, "accepted_answers": [3]}), "secret").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        
+        let (status, body) = post_admin(&mut app, "/api/startQuestion", json!({"question_type": "NormalQuestion", "category": "Live",
+            "question": "Inline question", "answers": ["A1", "A2"], "correct_answer": 2}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "3");
+        assert!(question_state(&data).await == QuestionState::NormalQAnswering(false));
+        match &data.game_events.read().await.last().unwrap().event
         {
-            player.jokers = params.jokers;
-            return HttpResponse::Ok().json(SetJokersData {name: player.name.clone(), jokers: player.jokers});
-        }
+            EventType::BeginNormalQAnswering(begin) => {
+                assert_eq!(begin.question, "Inline question");
+                assert_eq!(begin.current_question, 3);
+            },
+            _ => panic!("BeginNormalQAnswering expected"),
+        };
+        assert_eq!(answer(&mut app, "Alice", 2).await, StatusCode::OK);
+        tick(&data).await;
+        assert_eq!(data.player_data.read().await[0].money, 1000);
     }
     
-    HttpResponse::BadRequest().body("Player name was not found!")
-}
-
-//kick a player
-#[derive(Serialize, Deserialize)]
-struct KickPlayerData
-{
-    name: String,
-}
-#[get("/api/kickPlayer")]
-async fn kick_player(data: web::Data<GameshowData>, params: web::Query<KickPlayerData>) -> impl Responder
-{
-    let mut access = data.player_data.write().await;
-    
-    let len = (*access).len();
-    (*access).retain(|player| player.name != params.name);
-    if (*access).len() == len
-    { //player was not found
-        return HttpResponse::BadRequest().body("Player name was not found!");
+    #[actix_rt::test]
+    async fn exhausted_time_budget_skips_the_player()
+    {
+        let mut config = test_config();
+        config.time_budget_ms = 10_000;
+        let data = test_data(config, vec![normal_question(1); 3]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Slow").await;
+        //the first slow answer uses up 7 of the 10 seconds, the second one is too late
+        for round in 0 .. 2
+        {
+            next_question(&mut app, &data).await;
+            assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+            *data.state_began_at.lock().await = Instant::now() - Duration::from_secs(7);
+            let expected = if round == 0 { StatusCode::OK } else { StatusCode::FORBIDDEN };
+            assert_eq!(answer(&mut app, "Slow", 1).await, expected);
+            tick(&data).await;
+        }
+        let (_, body) = get(&mut app, "/api/getPlayerData").await;
+        let players: Vec<PlayerData> = serde_json::from_str(&body).unwrap();
+        assert_eq!(players[1].time_budget_ms, Some(0));
+        
+        //in the next round the slow player is skipped
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Slow", 1).await, StatusCode::LOCKED);
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        assert!(question_state(&data).await == QuestionState::NormalQAnswering(true));
     }
     
-    HttpResponse::Ok().finish()
-}
-
-//activate next question, will fail if current question was not finished
-#[get("/api/activateNextQuestion")]
-async fn activate_next_question(data: web::Data<GameshowData>) -> impl Responder
-{
-    //check if game state is ready for next question
-    let mut access = data.current_question_state.write().await;
-    if let QuestionState::Results(_) = *access
-    { //indicate possible transition to next question for automatic switch
-        *access = QuestionState::Results(true);
-        return HttpResponse::Ok().finish();
+    #[actix_rt::test]
+    async fn new_game_starts_again_after_the_game_ending()
+    {
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        next_question(&mut app, &data).await;
+        assert!(question_state(&data).await == QuestionState::GameEnding);
+        
+        let (status, _) = post_admin(&mut app, "/api/newGame", json!({"reset_players": true}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(question_state(&data).await == QuestionState::Results(false));
+        assert_eq!(data.current_question.load(Ordering::Relaxed), 0);
+        assert_eq!(event_names(&data).await.last().map(String::as_str), Some("NewGameStarted"));
+        assert_eq!(data.player_data.read().await[0].money, INITIAL_MONEY);
+        next_question(&mut app, &data).await;
+        assert!(question_state(&data).await == QuestionState::NormalQAnswering(false));
+        assert_eq!(data.current_question.load(Ordering::Relaxed), 1);
     }
-    else
+    
+    //play a normal question with a correct answer and spin the bonus wheel afterwards
+    async fn seeded_spin(seed: u64) -> SpinBonusResult
     {
-        return HttpResponse::NotAcceptable().body("QuestionState is not Results! => Not ready for next question!");
+        let mut config = test_config();
+        config.rng_seed = Some(seed);
+        config.bonus_wheel = vec![0.5, 1.0, 2.0, 3.0];
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        
+        let (status, body) = get(&mut app, "/api/spinBonus?name=Alice").await;
+        assert_eq!(status, StatusCode::OK);
+        let spin: SpinBonusResult = serde_json::from_str(&body).unwrap();
+        assert_eq!(data.player_data.read().await[0].money, spin.money);
+        match &data.game_events.read().await.last().unwrap().event
+        {
+            EventType::BonusSpin(bonus) => assert_eq!(bonus.multiplier, spin.multiplier),
+            _ => panic!("BonusSpin expected"),
+        };
+        let (status, _) = get(&mut app, "/api/spinBonus?name=Alice").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        spin
     }
-}
-
-//force end of betting and activate question answering
-#[get("/api/forceQuestionAnswering")]
-async fn force_question_answering(data: web::Data<GameshowData>) -> impl Responder
-{
-    //ensure current question state is betting or selecting, else return not acceptable
-    let mut question_state = data.current_question_state.write().await;
-    match *question_state
+    
+    #[actix_rt::test]
+    async fn seeded_bonus_spin_multiplies_the_gain_once()
     {
-        QuestionState::BettingQBetting(false) => { *question_state = QuestionState::BettingQBetting(true); },
-        QuestionState::VersusQSelecting(false) => { *question_state = QuestionState::VersusQSelecting(true); },
-        _ => { return HttpResponse::NotAcceptable().body("QuestionState is not Betting(false) or Selecting(false)!"); },
+        let spin = seeded_spin(9).await;
+        assert!([0.5, 1.0, 2.0, 3.0].contains(&spin.multiplier));
+        assert_eq!(spin.money, 500 + (500.0 * spin.multiplier) as i64);
+        let again = seeded_spin(9).await;
+        assert_eq!((again.multiplier, again.money), (spin.multiplier, spin.money));
     }
-    HttpResponse::Ok().finish()
-}
-
-//force end of question answering and show results
-#[get("/api/forceQuestionResults")]
-async fn force_question_results(data: web::Data<GameshowData>) -> impl Responder
-{
-    //ensure current question state is answering, else return not acceptable
-    let mut question_state = data.current_question_state.write().await;
-    match *question_state
+    
+    #[actix_rt::test]
+    async fn recompute_results_discards_the_spun_bonus()
     {
-        QuestionState::NormalQAnswering(false) => { *question_state = QuestionState::NormalQAnswering(true) },
-        QuestionState::BettingQAnswering(false) => { *question_state = QuestionState::BettingQAnswering(true); },
-        QuestionState::EstimationQAnswering(false) => { *question_state = QuestionState::EstimationQAnswering(true); },
-        QuestionState::VersusQAnswering(false) => { *question_state = QuestionState::VersusQAnswering(true); },
-        _ => { return HttpResponse::NotAcceptable().body("QuestionState is not *Answering(false)!"); },
+        let mut config = test_config();
+        config.bonus_wheel = vec![2.0];
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        let (status, _) = get(&mut app, "/api/spinBonus?name=Alice").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(data.player_data.read().await[0].money, 1500);
+        
+        let (status, _) = post_admin(&mut app, "/api/recomputeResults", json!({}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(data.player_data.read().await[0].money, 1000);
+        let (status, body) = get(&mut app, "/api/spinBonus?name=Alice").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(serde_json::from_str::<SpinBonusResult>(&body).unwrap().money, 1500);
     }
-    HttpResponse::Ok().finish()
-}
-
-//set the next question (only possible, when currently in results state)
-#[derive(Serialize, Deserialize)]
-struct SetNextQuestionData
-{
-    number: usize,
-}
-#[get("/api/setNextQuestion")]
-async fn set_next_question(data: web::Data<GameshowData>, params: web::Query<SetNextQuestionData>) -> impl Responder
-{
-    //ensure current question state is results or ended game, else return not acceptable; hold the lock until finished this time
-    let mut question_state = data.current_question_state.write().await;
-    if *question_state != QuestionState::Results(false) && *question_state != QuestionState::GameEnding
+    
+    #[actix_rt::test]
+    async fn answer_time_overrides_the_global_timeout()
     {
-        return HttpResponse::NotAcceptable().body("QuestionState is not Results(false) or GameEnding!");
+        let mut config = test_config();
+        config.global_question_timeout_secs = 30;
+        let quick = test_question(json!({"question_type": "NormalQuestion", "category": "Test", "question": "Test question",
+            "answers": ["A1", "A2"], "correct_answer": 1, "answer_time_secs": 5}));
+        let data = test_data(config, vec![quick, normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        
+        next_question(&mut app, &data).await;
+        *data.state_began_at.lock().await = Instant::now() - Duration::from_secs(6);
+        tick(&data).await;
+        tick(&data).await;
+        assert!(question_state(&data).await == QuestionState::Results(false));
+        
+        next_question(&mut app, &data).await;
+        *data.state_began_at.lock().await = Instant::now() - Duration::from_secs(6);
+        tick(&data).await;
+        assert!(question_state(&data).await == QuestionState::NormalQAnswering(false));
+        *data.state_began_at.lock().await = Instant::now() - Duration::from_secs(31);
+        tick(&data).await;
+        tick(&data).await;
+        assert!(question_state(&data).await == QuestionState::Results(false));
     }
     
-    let questions = data.questions.read().await;
-    if params.number < 1 || params.number > (*questions).len()
+    #[actix_rt::test]
+    async fn validate_questions_reports_every_error()
     {
-        return HttpResponse::BadRequest().body("Number is not a valid question ID (must be 1 - len(questions))!");
+        let mut config = test_config();
+        config.admin_token = Some(String::from("secret"));
+        let data = test_data(config, vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        let questions = json!([
+            {"question_type": "NormalQuestion", "category": "Test", "question": "Q1", "answers": ["A1", "A2"], "correct_answer": 1, "accepted_answers": [3]},
+            {"question_type": "NormalQuestion", "category": "Test", "question": "Q2", "answers": ["A1", "A2"], "correct_answer": 1},
+            {"question_type": "BettingQuestion", "category": "Test", "question": "Q3", "answers": ["A1", "A2"], "correct_answer": 1, "steal": true}
+        ]);
+        let (status, body) = post_admin(&mut app, "/api/validateQuestions", json!({"questions": questions}), "secret").await;
+        assert_eq!(status, StatusCode::OK);
+        let report: ValidationReport = serde_json::from_str(&body).unwrap();
+        assert!(!report.ok);
+        let errors: Vec<(Option<usize>, &str)> = report.errors.iter().map(|error| (error.question, error.reason.as_str())).collect();
+        assert_eq!(errors, vec![(Some(1), "accepted_answers must be 1 - len(answers)!"), (Some(3), "steal is only possible for normal questions!")]);
+        //the loaded questions are not touched
+        assert_eq!(data.questions.read().await.len(), 1);
+        
+        let (_, body) = post_admin(&mut app, "/api/validateQuestions", json!({"filename": "questions-example.json"}), "secret").await;
+        assert!(serde_json::from_str::<ValidationReport>(&body).unwrap().ok);
+        for filename in ["../.env", "Questions/questions-example.json", "..\\.env"].iter()
+        {
+            let (status, _) = post_admin(&mut app, "/api/validateQuestions", json!({"filename": filename}), "secret").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+        let (status, _) = post(&mut app, "/api/validateQuestions", json!({"filename": "questions-example.json"})).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
     }
-    else
+    
+    #[actix_rt::test]
+    async fn streak_bonus_applies_at_the_milestone()
     {
-        let question_id = data.current_question.swap(params.number - 1, Ordering::Relaxed);
-        *question_state = QuestionState::Results(false);
-        return HttpResponse::Ok().body(question_id.to_string());
+        let mut config = test_config();
+        config.streak_bonus = vec![(3, 100)];
+        let data = test_data(config, vec![normal_question(1); 4]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        let mut streaks = Vec::new();
+        for given in [1, 1, 1, 2].iter()
+        {
+            next_question(&mut app, &data).await;
+            assert_eq!(answer(&mut app, "Alice", *given).await, StatusCode::OK);
+            tick(&data).await;
+            let (_, body) = get(&mut app, "/api/getPlayerData").await;
+            let players: Vec<PlayerData> = serde_json::from_str(&body).unwrap();
+            streaks.push((players[0].streak, players[0].money));
+        }
+        assert_eq!(streaks, vec![(1, 1000), (2, 1500), (3, 2000 + 100), (0, 2100)]);
     }
-}
-
-//load questions from a the given filename
-#[derive(Serialize, Deserialize)]
-struct LoadQuestions
-{
-    filename: String,
-}
-#[post("/api/loadQuestions")]
-async fn load_questions(data: web::Data<GameshowData>, params: web::Json<LoadQuestions>) -> impl Responder
-{
-    //ensure current question state is results or ended game, else return not acceptable; hold the lock until finished this time
-    let mut question_state = data.current_question_state.write().await;
-    if *question_state != QuestionState::Results(false) && *question_state != QuestionState::GameEnding
+    
+    #[actix_rt::test]
+    async fn streak_is_kept_while_not_taking_part()
     {
-        return HttpResponse::NotAcceptable().body("QuestionState is not Results(false) or GameEnding!");
+        let data = test_data(test_config(), vec![normal_question(1); 2]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Bob", 1).await, StatusCode::OK);
+        tick(&data).await;
+        data.player_data.write().await[1].disabled = true;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        tick(&data).await;
+        
+        let player_data = data.player_data.read().await;
+        assert_eq!((player_data[0].streak, player_data[0].answered_count), (2, 2));
+        assert_eq!((player_data[1].streak, player_data[1].answered_count), (1, 1));
     }
     
-    let new_questions = read_questions(String::from("./Questions/") + &params.filename).await;
-    if new_questions.is_err()
+    #[actix_rt::test]
+    async fn open_betting_shows_the_bets_while_answering()
     {
-        return HttpResponse::BadRequest().body("Question file could not be loaded!");
+        for &open_betting in [false, true].iter()
+        {
+            let mut config = test_config();
+            config.open_betting = open_betting;
+            config.admin_token = Some(String::from("secret"));
+            let question = test_question(json!({"question_type": "BettingQuestion", "category": "Test", "question": "Test question",
+                "answers": ["A1", "A2"], "correct_answer": 1}));
+            let data = test_data(config, vec![question]);
+            let mut app = test_app(&data).await;
+            join(&mut app, "Alice").await;
+            next_question(&mut app, &data).await;
+            let (status, _) = get(&mut app, "/api/betMoney?name=Alice&money_bet=150").await;
+            assert_eq!(status, StatusCode::OK);
+            //the bets are always hidden while betting
+            let (_, body) = get(&mut app, "/api/getPlayerData").await;
+            assert_eq!(serde_json::from_str::<Vec<PlayerData>>(&body).unwrap()[0].money_bet, 0);
+            //only the host sees them in the full player data
+            let (status, _) = get(&mut app, "/api/getPlayerDataFull").await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+            let (_, body) = get_admin(&mut app, "/api/getPlayerDataFull", "secret").await;
+            assert_eq!(serde_json::from_str::<Vec<PlayerData>>(&body).unwrap()[0].money_bet, 150);
+            begin_betting_answering(&data).await;
+            
+            let (_, body) = get(&mut app, "/api/getPlayerData").await;
+            let visible_bet = serde_json::from_str::<Vec<PlayerData>>(&body).unwrap()[0].money_bet;
+            let event_bets = match &data.game_events.read().await.last().unwrap().event
+            {
+                EventType::BeginBettingQAnswering(begin) => begin.bets.clone(),
+                _ => panic!("BeginBettingQAnswering expected"),
+            };
+            if open_betting
+            {
+                assert_eq!(visible_bet, 150);
+                assert_eq!(event_bets, Some(vec![(String::from("Alice"), 150)]));
+            }
+            else
+            {
+                assert_eq!(visible_bet, 0);
+                assert_eq!(event_bets, None);
+            }
+        }
     }
-    else
+    
+    #[actix_rt::test]
+    async fn player_events_mention_the_player()
     {
-        let mut questions = data.questions.write().await;
-        (*questions) = new_questions.ok().unwrap();
-        data.current_question.store(0, Ordering::Relaxed);
-        *question_state = QuestionState::Results(false);
-        return HttpResponse::Ok().body((*questions).len().to_string());
+        let data = test_data(test_config(), vec![normal_question(1)]);
+        let mut app = test_app(&data).await;
+        join(&mut app, "Alice").await;
+        join(&mut app, "Bob").await;
+        next_question(&mut app, &data).await;
+        assert_eq!(answer(&mut app, "Alice", 1).await, StatusCode::OK);
+        assert_eq!(answer(&mut app, "Bob", 2).await, StatusCode::OK);
+        tick(&data).await;
+        
+        let (status, body) = get(&mut app, "/api/playerEvents?name=Alice").await;
+        assert_eq!(status, StatusCode::OK);
+        let events: Vec<Event> = serde_json::from_str(&body).unwrap();
+        let names: Vec<&str> = events.iter().map(|event| event.event_name.as_str()).collect();
+        assert!(names.contains(&"PlayerJoined") && names.contains(&"RoundSummary"));
+        assert!(events.iter().all(|event| event_mentions_player(&event.event, "Alice")));
+        assert!(!events.iter().any(|event| matches!(&event.event, EventType::PlayerJoined(joined) if joined.name == "Bob")));
+        
+        let (_, body) = get(&mut app, "/api/playerEvents?name=Bob").await;
+        let events: Vec<Event> = serde_json::from_str(&body).unwrap();
+        assert!(!events.iter().any(|event| event.event_name == "RoundSummary"));
     }
-}
-
-
-
-#[actix_web::main]
-async fn main() -> std::io::Result<()>
-{
-    dotenv().ok();
-
-    let questions_file = env::var("QUESTIONS_FILE").unwrap_or(String::from(QUESTIONS_FILE));
-    let questions = read_questions(questions_file).await?;
     
-    let data = web::Data::new(GameshowData {
-        player_data: RwLock::new(Vec::new()),
-        questions: RwLock::new(questions),
-        game_events: RwLock::new(Vec::new()),
-        current_question: AtomicUsize::new(0),
-        current_question_state: RwLock::new(QuestionState::Results(false)),
-    });
-
-    HttpServer::new(move || {
-        App::new()
-            //shared data to store the gameshow state etc.
-            .app_data(data.clone())
-
-            //service the API sites/functions
-            .service(index)
-            .service(join_player)
-            .service(get_player_data)
-            .service(bet_money)
-            .service(attack_player)
-            .service(answer_question)
-            .service(get_joker_fifty_fifty)
-            .service(get_game_events)
-            .service(give_money)
-            .service(set_jokers)
-            .service(kick_player)
-            .service(activate_next_question)
-            .service(force_question_answering)
-            .service(force_question_results)
-            .service(set_next_question)
-            .service(load_questions)
-
-            //publish the gameshow's frontend (static files)
-            //(must be last, so that the specific handlers are served)
-            .service(actix_files::Files::new("/", "./Gameshow").index_file("play.htm"))
-            //.service(actix_files::Files::new("/", "./Gameshow").show_files_listing())
-    })
-    .bind("127.0.0.1:8000")?
-    .run()
-    .await
+    #[test]
+    fn concurrent_polling_transitions_once()
+    {
+        let data = test_data(test_config(), vec![normal_question(1), normal_question(1)]);
+        let setup_data = data.clone();
+        actix_rt::System::new("setup").block_on(async move {
+            let data = setup_data;
+            let mut app = test_app(&data).await;
+            join(&mut app, "Alice").await;
+            let (status, _) = get(&mut app, "/api/activateNextQuestion").await;
+            assert_eq!(status, StatusCode::OK);
+        });
+        
+        //several threads hammer getGameEvents and run the transitions at the same time
+        let threads: Vec<std::thread::JoinHandle<()>> = (0 .. 8).map(|i| {
+            let data = data.clone();
+            std::thread::spawn(move || {
+                actix_rt::System::new(format!("worker{}", i)).block_on(async move {
+                    let mut app = test_app(&data).await;
+                    for _ in 0 .. 50
+                    {
+                        let (status, _) = get(&mut app, "/api/getGameEvents").await;
+                        assert_eq!(status, StatusCode::OK);
+                        tick(&data).await;
+                    }
+                });
+            })
+        }).collect();
+        for thread in threads
+        {
+            thread.join().unwrap();
+        }
+        
+        actix_rt::System::new("check").block_on(async move {
+            assert_eq!(data.current_question.load(Ordering::Relaxed), 1);
+            assert!(question_state(&data).await == QuestionState::NormalQAnswering(false));
+            let names = event_names(&data).await;
+            assert_eq!(names.iter().filter(|name| *name == "BeginNormalQAnswering").count(), 1);
+            let ids: Vec<usize> = data.game_events.read().await.iter().map(|event| event.id).collect();
+            assert_eq!(ids, (0 .. ids.len()).collect::<Vec<usize>>());
+        });
+    }
 }
-